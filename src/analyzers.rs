@@ -9,37 +9,121 @@ pub mod var_decl;
 pub mod bit_field;
 pub mod cmnt_struct;
 pub mod func_call;
+pub mod pipe_table;
+pub mod csv;
+pub mod rust_decl;
+pub mod match_arm;
+pub mod continuation;
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum AnalyzeErrKind {
+    Missing,       //expected token wasn't found
+    Unexpected,    //other token than expected at the position
+    InvalidNumber,
+    Rejected,      //the line doesn't have the shape the analyzer expects
+    Unsupported,   //analyzer can't handle lines at all
+}
 
 #[derive(Debug)]
 pub struct AnalyzeErr{
-    
+    pub kind : AnalyzeErrKind,
+    pub offset : Option<usize>,    //byte offset in the line where the analysis stopped
+    pub expected : Option<String>, //token or shape the analyzer expected
+    pub source : Option<Box<dyn std::error::Error + Send + Sync>>,
+}
+
+impl AnalyzeErr {
+    pub fn new(kind : AnalyzeErrKind) -> Self
+    {
+        Self{kind, offset : None, expected : None, source : None}
+    }
+
+    pub fn missing(expected : &str, offset : usize) -> Self
+    {
+        Self::new(AnalyzeErrKind::Missing).expected(expected).at(offset)
+    }
+
+    pub fn unexpected(expected : &str, offset : usize) -> Self
+    {
+        Self::new(AnalyzeErrKind::Unexpected).expected(expected).at(offset)
+    }
+
+    pub fn rejected(expected : &str, offset : usize) -> Self
+    {
+        Self::new(AnalyzeErrKind::Rejected).expected(expected).at(offset)
+    }
+
+    pub fn expected(mut self, expected : &str) -> Self
+    {
+        self.expected = Some(expected.to_string());
+        self
+    }
+
+    pub fn at(mut self, offset : usize) -> Self
+    {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Makes the offset relative to the outer string, for errors from a substring starting at `base`
+    pub fn shift(mut self, base : usize) -> Self
+    {
+        self.offset = self.offset.map(|o|o + base);
+        self
+    }
 }
 
 impl std::fmt::Display for AnalyzeErr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "analyzer error")
+        let exp = self.expected.as_deref().unwrap_or("");
+        match self.kind {
+            AnalyzeErrKind::Missing => write!(f, "missing {}", exp)?,
+            AnalyzeErrKind::Unexpected => write!(f, "expected {}", exp)?,
+            AnalyzeErrKind::InvalidNumber => match &self.expected {
+                Some(e) => write!(f, "invalid number, expected {}", e)?,
+                None => write!(f, "invalid number")?,
+            },
+            AnalyzeErrKind::Rejected => write!(f, "not accepted, expected {}", exp)?,
+            AnalyzeErrKind::Unsupported => write!(f, "analyzer doesn't support lines")?,
+        }
+        if let Some(o) = self.offset {
+            write!(f, " at byte {}", o)?;
+        }
+        if let Some(src) = &self.source {
+            write!(f, ": {}", src)?;
+        }
+        Ok(())
     }
 }
 
 impl std::error::Error for AnalyzeErr {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.as_ref().map(|e|e.as_ref() as &(dyn std::error::Error + 'static))
+    }
 }
 
 impl std::convert::From<std::num::ParseIntError> for AnalyzeErr{
-    fn from(_err : std::num::ParseIntError) -> Self
+    fn from(err : std::num::ParseIntError) -> Self
     {
-        Self{}
+        Self{kind : AnalyzeErrKind::InvalidNumber, offset : None, expected : None, source : Some(Box::new(err))}
     }
 }
 
 pub trait LineAnalyzer
 {
     fn clear(&mut self){}
-    fn can_accept(&self, _s :&str)->Result<(),AnalyzeErr> {Err(AnalyzeErr{})}
-    fn analyze_line<'a>(&mut self, _fmt :&mut Formatter, _l: &mut LineDescr<'a>)->Result<(),AnalyzeErr>{Err(AnalyzeErr{})}
+    //forgets what was carried over from the previous lines, before another pass over the input
+    fn reset_state(&mut self){}
+    fn can_accept(&self, _s :&str)->Result<(),AnalyzeErr> {Err(AnalyzeErr::new(AnalyzeErrKind::Unsupported))}
+    fn analyze_line<'a>(&mut self, _fmt :&mut Formatter, _l: &mut LineDescr<'a>)->Result<(),AnalyzeErr>{Err(AnalyzeErr::new(AnalyzeErrKind::Unsupported))}
     fn type_name(&self)->&'static str {std::any::type_name::<Self>()}
-    fn parse_args(&mut self, _arg_it :std::slice::Iter<String>) -> Result<(), Box<dyn std::error::Error>> {Ok(())}
 }
 
+/// Whitespace helpers accept any unicode whitespace.
+/// `rfind_*` return the last byte of the found char, `find_*` and `sym` its first byte.
+/// Errors point to where the search gave up: the end of the string, where backward searches start and forward ones stop.
 pub trait LineParser
 {
     fn rfind_nwhite(&self)->Result<usize, AnalyzeErr>;
@@ -53,24 +137,30 @@ pub trait LineParser
     fn rfind_str(&self, s: &str)->Result<usize, AnalyzeErr>;
 }
 
+//position of the last byte of a found char, so that 'pos + 1' is always a char boundary.
+//A failed backward search points at its start, the end of the searched text
+fn last_byte_of(found :Option<(usize, char)>, expected :&str, searched :&str)->Result<usize, AnalyzeErr> {
+    found.map(|(i, c)|i + c.len_utf8() - 1).ok_or_else(||AnalyzeErr::missing(expected, searched.len()))
+}
+
 impl LineParser for str {
     fn rfind_nwhite(&self)->Result<usize, AnalyzeErr> {
-        self.rfind(|c:char|!c.is_ascii_whitespace()).ok_or(AnalyzeErr{})
+        last_byte_of(self.char_indices().rev().find(|(_, c)|!c.is_whitespace()), "non-whitespace", self)
     }
     fn rfind_white(&self)->Result<usize, AnalyzeErr> {
-        self.rfind(|c:char|c.is_ascii_whitespace()).ok_or(AnalyzeErr{})
+        last_byte_of(self.char_indices().rev().find(|(_, c)|c.is_whitespace()), "whitespace", self)
     }
     fn find_nwhite(&self)->Result<usize, AnalyzeErr>{
-        self.find(|c:char|!c.is_ascii_whitespace()).ok_or(AnalyzeErr{})
+        self.find(|c:char|!c.is_whitespace()).ok_or_else(||AnalyzeErr::missing("non-whitespace", self.len()))
     }
     fn find_white(&self)->Result<usize, AnalyzeErr> {
-        self.find(|c:char|c.is_ascii_whitespace()).ok_or(AnalyzeErr{})
+        self.find(|c:char|c.is_whitespace()).ok_or_else(||AnalyzeErr::missing("whitespace", self.len()))
     }
     fn sym(&self, c: char)->Result<usize, AnalyzeErr> {
-        self.find(c).ok_or(AnalyzeErr{})
+        self.find(c).ok_or_else(||AnalyzeErr::missing(&format!("'{}'", c), self.len()))
     }
     fn rsym(&self, c: char)->Result<usize, AnalyzeErr> {
-        self.rfind(c).ok_or(AnalyzeErr{})
+        self.rfind(c).ok_or_else(||AnalyzeErr::missing(&format!("'{}'", c), self.len()))
     }
 
     fn expect_sym(&self, c : char)->Result<(), AnalyzeErr>
@@ -80,16 +170,16 @@ impl LineParser for str {
                 return Ok(());
             }
         }
-        return Err(AnalyzeErr{});
+        Err(AnalyzeErr::unexpected(&format!("'{}'", c), 0))
     }
 
     fn find_str(&self, s: &str)->Result<usize, AnalyzeErr>
     {
-        self.find(s).ok_or(AnalyzeErr{})
+        self.find(s).ok_or_else(||AnalyzeErr::missing(&format!("\"{}\"", s), self.len()))
     }
 
     fn rfind_str(&self, s: &str)->Result<usize, AnalyzeErr>
     {
-        self.rfind(s).ok_or(AnalyzeErr{})
+        self.rfind(s).ok_or_else(||AnalyzeErr::missing(&format!("\"{}\"", s), self.len()))
     }
 }
\ No newline at end of file
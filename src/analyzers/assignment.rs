@@ -24,22 +24,25 @@ impl TypeVarAnalyzer
    fn find_key_points(&self, s :&str)->Result<TypeVarKeyPoints, AnalyzeErr> 
    {
         let assign_pos = s.sym('=')?;
+        if s[assign_pos..].starts_with("=>") {
+             return Err(AnalyzeErr::rejected("'=' instead of '=>'", assign_pos));
+        }
         if assign_pos + 1 >= s.len() {
-             return Err(AnalyzeErr{}); 
+             return Err(AnalyzeErr::missing("expression after '='", assign_pos + 1));
         }
         lazy_static! {
             static ref RE:Regex = Regex::new(r"\)\s*(const)?\s*=\s*0;").unwrap();
         }
-        if let Some(_) = RE.find(s) {
-             return Err(AnalyzeErr{}); 
+        if let Some(m) = RE.find(s) {
+             return Err(AnalyzeErr::rejected("no pure virtual declaration", m.start()));
         }
         
         let var_end = s[..assign_pos].rfind_nwhite()?;
-        let var_begin = s[..var_end].rfind_white()? + 1;
+        let var_begin = s[..=var_end].rfind_white()? + 1;
         let type_end = s[..var_begin].rfind_nwhite()?;
         let type_begin = s.find_nwhite()?;
 
-        let expr_begin = s[assign_pos + 1..].find_nwhite()? + assign_pos + 1;
+        let expr_begin = s[assign_pos + 1..].find_nwhite().map_err(|e|e.shift(assign_pos + 1))? + assign_pos + 1;
 
         Ok(TypeVarKeyPoints{var_begin, var_end, type_begin, type_end, expr_begin})
    }
@@ -84,12 +87,13 @@ impl VarAnalyzer
    fn find_key_points(&self, s :&str)->Result<VarKeyPoints, AnalyzeErr> 
    {
         let assign_pos = s.sym('=')?;
-        if assign_pos + 1 >= s.len() { return Err(AnalyzeErr{}); }
+        if s[assign_pos..].starts_with("=>") { return Err(AnalyzeErr::rejected("'=' instead of '=>'", assign_pos)); }
+        if assign_pos + 1 >= s.len() { return Err(AnalyzeErr::missing("expression after '='", assign_pos + 1)); }
         
         let before_end = s[..assign_pos].rfind_nwhite()?;
         let before_begin = s[..assign_pos].find_white()?;
-        let after_begin = s[assign_pos + 1..].find_nwhite()? + assign_pos + 1;
-        let after_end = s[assign_pos + 1..].rfind_nwhite()? + assign_pos + 1;
+        let after_begin = s[assign_pos + 1..].find_nwhite().map_err(|e|e.shift(assign_pos + 1))? + assign_pos + 1;
+        let after_end = s[assign_pos + 1..].rfind_nwhite().map_err(|e|e.shift(assign_pos + 1))? + assign_pos + 1;
 
         Ok(VarKeyPoints{before_begin, before_end, after_begin, after_end})
    }
@@ -26,16 +26,16 @@ impl Analyzer
         let colon_pos = s.sym(':')?;
         
         let var_end = s[..colon_pos].rfind_nwhite()?;
-        let var_begin = s[..var_end].rfind_white()? + 1;
+        let var_begin = s[..=var_end].rfind_white()? + 1;
         let type_end = s[..var_begin].rfind_nwhite()?;
         let type_begin = s.find_nwhite()?;
-        let bit_begin = s[colon_pos + 1..].find_nwhite()? + colon_pos + 1;
-        let bit_end = s[bit_begin..].sym(';')? + bit_begin;
-        s[bit_begin..bit_end].parse::<u32>()?;
+        let bit_begin = s[colon_pos + 1..].find_nwhite().map_err(|e|e.shift(colon_pos + 1))? + colon_pos + 1;
+        let bit_end = s[bit_begin..].sym(';').map_err(|e|e.shift(bit_begin))? + bit_begin;
+        s[bit_begin..bit_end].parse::<u32>().map_err(|e|AnalyzeErr::from(e).expected("bit count").at(bit_begin))?;
         let t = if bit_end + 1 < s.len() {
             s[bit_end + 1..].find_nwhite()
         }else {
-           Err(AnalyzeErr{}) 
+           Err(AnalyzeErr::missing("text after ';'", s.len()))
         };
 
         let rest_begin;
@@ -23,6 +23,12 @@ struct KeyPoints
     block_begin : usize,
 }
 
+impl Default for Analyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Analyzer {
     pub fn new()->Self
     {
@@ -37,7 +43,7 @@ impl Analyzer {
         let block_end = s.rsym('}')?;
         
         if c_begin > c_end || block_begin < c_end || block_end < block_begin {
-            Err(AnalyzeErr{})
+            Err(AnalyzeErr::rejected("'/* */' comment before '{ }' block", c_begin))
         }else {
             Ok(KeyPoints{c_begin, c_end, block_begin})
         }
@@ -0,0 +1,74 @@
+use super::LineAnalyzer;
+use super::AnalyzeErr;
+use crate::column_tools::Align;
+use crate::column_tools::ColumnStyle;
+use crate::column_tools::Formatter;
+use crate::column_tools::LineDescr;
+use crate::column_tools::ParseErr;
+
+/// Lines continued with a trailing `\`: multi-line `#define` bodies, shell commands.
+/// The content, indentation included, is a single column kept as is and the `\` is its separator,
+/// so all the backslashes of a block end up in the same column.
+/// Lines without the continuation (like the last line of a macro) are rejected and left alone.
+pub struct Analyzer {
+}
+
+/// Where the continuation backslashes go, parsed from `shared`, `tab` or the column number
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum ContinuationColumn {
+    Shared,       //one space past the longest line of the block
+    Fixed(usize), //given number of cells before the backslash, one space past longer lines
+    TabStop,      //first tab stop past the longest line, reached with a tab
+}
+
+impl ContinuationColumn {
+    /// Style of the content column that puts the backslashes there
+    pub fn column_style(&self) -> ColumnStyle
+    {
+        let st = ColumnStyle::new(0).align(Align::Left);
+        match self {
+            ContinuationColumn::Shared => st.sep(' ', 1, Align::Right),
+            ContinuationColumn::Fixed(n) => st.min(n.saturating_sub(1)).sep(' ', 1, Align::Right),
+            ContinuationColumn::TabStop => st.sep('\t', 1, Align::Right),
+        }
+    }
+}
+
+impl std::str::FromStr for ContinuationColumn {
+    type Err = ParseErr;
+
+    fn from_str(s :&str) -> Result<Self, Self::Err>
+    {
+        match s.to_lowercase().as_str() {
+            "shared" => Ok(ContinuationColumn::Shared),
+            "tab" => Ok(ContinuationColumn::TabStop),
+            n => n.parse::<usize>().map(ContinuationColumn::Fixed).map_err(|_|ParseErr::new("shared, tab or column number", s)),
+        }
+    }
+}
+
+//end of the content before the trailing '\'
+fn content_end(s :&str) -> Result<usize, AnalyzeErr>
+{
+    match s.trim_end().strip_suffix('\\') {
+        Some(content) => Ok(content.trim_end().len()),
+        None => Err(AnalyzeErr::missing("'\\' at the end of the line", s.trim_end().len())),
+    }
+}
+
+impl LineAnalyzer for Analyzer {
+    fn can_accept(&self, s :&str)->Result<(),AnalyzeErr>
+    {
+        content_end(s)?;
+        Ok(())
+    }
+
+    fn analyze_line<'a>(&mut self, fmt :&mut Formatter, l: &mut LineDescr<'a>)->Result<(),AnalyzeErr>
+    {
+        let end = content_end(l.s)?;
+        fmt.add_column(0, end, '\\', l);
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+use super::LineAnalyzer;
+use super::AnalyzeErr;
+use crate::column_tools::Formatter;
+use crate::column_tools::LineDescr;
+
+/// RFC 4180 records: fields separated by the delimiter, optionally quoted.
+/// Inside quotes the delimiter is data and a quote is escaped by the escape char (by default by doubling it).
+/// Empty fields keep their columns, whitespace around the fields is padding and isn't measured.
+/// A quoted field may continue on the next lines: the fields before it are aligned,
+/// its opening part doesn't widen the column and the lines it continues on are kept as is,
+/// trailing whitespace inside the quotes included.
+pub struct Analyzer
+{
+    delimiter : char,
+    quote : char,
+    escape : char,
+    header : bool,
+    in_quote : bool,    //the previous line ended inside a quoted field
+    header_seen : bool,
+}
+
+impl Analyzer {
+    pub fn new(delimiter :char) -> Analyzer
+    {
+        Analyzer{delimiter, quote : '"', escape : '"', header : false, in_quote : false, header_seen : false}
+    }
+
+    /// Also sets the escape char, unless it is set afterwards
+    pub fn set_quote(&mut self, quote :char)
+    {
+        self.quote = quote;
+        self.escape = quote;
+    }
+
+    pub fn set_escape(&mut self, escape :char)
+    {
+        self.escape = escape;
+    }
+
+    /// The first record is a header: its cells don't keep the columns from being numeric
+    pub fn set_header(&mut self, header :bool)
+    {
+        self.header = header;
+    }
+
+    //fields of the line as (begin, end, delimiter after it) and whether the line ends inside quotes
+    fn split_fields(&self, s :&str, mut quoted :bool) -> (Vec<(usize, usize, char)>, bool)
+    {
+        let mut fields = Vec::new();
+        let mut begin = 0;
+        let mut it = s.char_indices().peekable();
+        while let Some((i, c)) = it.next() {
+            if quoted {
+                if c == self.escape && self.escape != self.quote {
+                    it.next();
+                }else if c == self.quote {
+                    if self.escape == self.quote && it.peek().map(|(_, n)|*n) == Some(self.quote) {
+                        it.next();
+                    }else{
+                        quoted = false;
+                    }
+                }
+            }else if c == self.delimiter {
+                fields.push((begin, i, c));
+                begin = i + c.len_utf8();
+            }else if c == self.quote && s[begin..i].trim().is_empty() {
+                //quotes in the middle of an unquoted field are data
+                quoted = true;
+            }
+        }
+        fields.push((begin, s.len(), '\0'));
+        (fields, quoted)
+    }
+}
+
+impl LineAnalyzer for Analyzer
+{
+    fn reset_state(&mut self)
+    {
+        self.in_quote = false;
+        self.header_seen = false;
+    }
+
+    fn can_accept(&self, _s :&str)->Result<(),AnalyzeErr>
+    {
+        //accept all
+        Ok(())
+    }
+
+    fn analyze_line<'a>(&mut self, fmt :&mut Formatter, l: &mut LineDescr<'a>)->Result<(),AnalyzeErr>
+    {
+        let continued = self.in_quote;
+        let (fields, open) = self.split_fields(l.s, continued);
+        self.in_quote = open;
+        if continued {
+            if open {
+                l.set_continues();
+            }
+            return Ok(());
+        }
+
+        let header = self.header && !self.header_seen;
+        self.header_seen = true;
+        let last = fields.len() - 1;
+        for (i, (begin, end, delim)) in fields.into_iter().enumerate() {
+            let field = &l.s[begin..end];
+            let b = end - field.trim_start().len();
+            let e = b + field.trim().len();
+            if open && i == last {
+                fmt.add_tail_column(b, e, delim, l);
+            }else if header {
+                fmt.add_header_column(b, e, delim, l);
+            }else{
+                fmt.add_column(b, e, delim, l);
+            }
+        }
+        Ok(())
+    }
+}
@@ -18,6 +18,12 @@ struct KeyPoints
     paren_pos : usize,
 }
 
+impl Default for Analyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Analyzer
 {
     pub fn new()->Self
@@ -28,18 +34,18 @@ impl Analyzer
    fn find_key_points(&self, s :&str)->Result<KeyPoints, AnalyzeErr> 
    {
         let paren_pos = s.sym('(')?;
-        if paren_pos + 1 >= s.len() { return Err(AnalyzeErr{}); }
+        if paren_pos + 1 >= s.len() { return Err(AnalyzeErr::missing("arguments after '('", paren_pos + 1)); }
         
-        if let Some(_) = s[..paren_pos].find('=') {
-            return Err(AnalyzeErr{});
+        if let Some(eq) = s[..paren_pos].find('=') {
+            return Err(AnalyzeErr::rejected("no '=' before '('", eq));
         }
 
-        if let Some(_) = s[..paren_pos].find(|c:char|{!(c.is_ascii_alphanumeric() || c==':' || c=='.' || c == '-' || c == '>' || c == '_' || c.is_whitespace())}) {
-            return Err(AnalyzeErr{});
+        if let Some(bad) = s[..paren_pos].find(|c:char|{!(c.is_ascii_alphanumeric() || c==':' || c=='.' || c == '-' || c == '>' || c == '_' || c.is_whitespace())}) {
+            return Err(AnalyzeErr::rejected("function name before '('", bad));
         }
         
         let fn_end = s[..paren_pos].rfind_nwhite()?;
-        let fn_begin = s[..fn_end].find_nwhite()?;
+        let fn_begin = s[..=fn_end].find_nwhite()?;
         
         Ok(KeyPoints{fn_begin, fn_end, paren_pos})
    }
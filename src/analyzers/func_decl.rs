@@ -21,14 +21,14 @@ impl Analyzer
    fn find_key_points(&self, s :&str)->Result<KeyPoints, AnalyzeErr> 
    {
         let paren_pos = s.sym('(')?;
-        if paren_pos + 1 >= s.len() { return Err(AnalyzeErr{}); }
+        if paren_pos + 1 >= s.len() { return Err(AnalyzeErr::missing("parameters after '('", paren_pos + 1)); }
         
-        if let Some(_) = s[..paren_pos].find('=') {
-            return Err(AnalyzeErr{});
+        if let Some(eq) = s[..paren_pos].find('=') {
+            return Err(AnalyzeErr::rejected("no '=' before '('", eq));
         }
         
         let fn_end = s[..paren_pos].rfind_nwhite()?;
-        let fn_begin = s[..fn_end].rfind_white()? + 1;
+        let fn_begin = s[..=fn_end].rfind_white()? + 1;
         let type_end = s[..fn_begin].rfind_nwhite()?;
         let type_begin = s.find_white()?;
 
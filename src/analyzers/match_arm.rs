@@ -0,0 +1,92 @@
+use super::LineAnalyzer;
+use super::AnalyzeErr;
+use super::LineParser;
+use crate::column_tools::Formatter;
+use crate::column_tools::LineDescr;
+
+/// Arms separated by `=>`: Rust `match` arms, PHP and Ruby hash entries, Scala cases.
+/// Columns: the pattern (guards included), the `=>` and the body (a `{` opening a block body too).
+/// Only a `=>` outside of brackets and quotes ends the pattern.
+pub struct Analyzer {
+}
+
+struct KeyPoints
+{
+    pat_begin : usize,
+    pat_end : usize,
+    arrow : usize,
+    body : Option<(usize, usize)>,
+}
+
+//first `=>` outside of brackets and quotes, a plain `=` before it makes the line an assignment
+fn find_arrow(s :&str) -> Result<usize, AnalyzeErr>
+{
+    let b = s.as_bytes();
+    let mut depth = 0i32;
+    let mut quote : Option<u8> = None;
+    let mut i = 0;
+    while i < b.len() {
+        match (quote, b[i]) {
+            (Some(_), b'\\') => i += 1,
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {},
+            (None, b'"') | (None, b'\'') => quote = Some(b[i]),
+            (None, b'(') | (None, b'[') | (None, b'{') => depth += 1,
+            (None, b')') | (None, b']') | (None, b'}') => depth -= 1,
+            (None, b'=') if depth == 0 => {
+                let next = b.get(i + 1).copied().unwrap_or(b' ');
+                if next == b'>' {
+                    return Ok(i);
+                }
+                let prev = if i > 0 { b[i - 1] } else { b' ' };
+                if next != b'=' && !matches!(prev, b'=' | b'!' | b'<' | b'>' | b'.') {
+                    return Err(AnalyzeErr::rejected("no '=' before '=>'", i));
+                }
+                //the second '=' of `==` mustn't look like an assignment
+                if next == b'=' {
+                    i += 1;
+                }
+            },
+            _ => {}
+        }
+        i += 1;
+    }
+    Err(AnalyzeErr::missing("'=>'", s.len()))
+}
+
+impl Analyzer
+{
+   fn find_key_points(&self, s :&str)->Result<KeyPoints, AnalyzeErr>
+   {
+        let arrow = find_arrow(s)?;
+        let pat_begin = s.find_nwhite()?;
+        if pat_begin == arrow {
+            return Err(AnalyzeErr::missing("pattern before '=>'", arrow));
+        }
+        let pat_end = s[..arrow].rfind_nwhite()? + 1;
+        let body = s[arrow + 2..].find_nwhite().ok().map(|b|(arrow + 2 + b, s.trim_end().len()));
+
+        Ok(KeyPoints{pat_begin, pat_end, arrow, body})
+   }
+}
+
+impl LineAnalyzer for Analyzer {
+
+    fn can_accept(&self, s :&str)->Result<(),AnalyzeErr>
+    {
+        self.find_key_points(s)?;
+        Ok(())
+    }
+
+    fn analyze_line<'a>(&mut self, fmt :&mut Formatter, l: &mut LineDescr<'a>)->Result<(),AnalyzeErr>
+    {
+        let KeyPoints{pat_begin, pat_end, arrow, body} = self.find_key_points(l.s)?;
+
+        fmt.add_column(pat_begin, pat_end, ' ', l);
+        fmt.add_column(arrow, arrow + 2, if body.is_some() { ' ' } else { '\0' }, l);
+        if let Some((body_begin, body_end)) = body {
+            fmt.add_column(body_begin, body_end, '\0', l);
+        }
+        Ok(())
+    }
+}
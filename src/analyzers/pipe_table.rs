@@ -0,0 +1,112 @@
+use super::LineAnalyzer;
+use super::AnalyzeErr;
+use super::LineParser;
+use crate::column_tools::Formatter;
+use crate::column_tools::LineDescr;
+
+/// Rows of Markdown, Org-mode and Gherkin tables: `| a | b |`.
+/// `|` ends a cell unless it is escaped (`\|`) or inside a backtick code span.
+/// A delimiter row (`|:---|---:|`, Org's `|----+----|`) is a rule: its cells are regenerated
+/// to the column widths and its colons set the alignment of the columns.
+pub struct Analyzer {
+}
+
+//end of the code span opened by the backtick run at `start`, None if it is never closed
+fn code_span_end(s :&str, start :usize) -> Option<usize>
+{
+    let run_end = |from :usize|s[from..].find(|c:char|c != '`').map_or(s.len(), |p|p + from);
+    let open_len = run_end(start) - start;
+    let mut pos = start + open_len;
+    while let Some(off) = s[pos..].find('`') {
+        let close = pos + off;
+        let close_end = run_end(close);
+        if close_end - close == open_len {
+            return Some(close_end);
+        }
+        pos = close_end;
+    }
+    None
+}
+
+//positions of the cell boundaries in s[start..] and the boundary chars
+fn boundaries(s :&str, start :usize, seps :&[u8]) -> Vec<(usize, char)>
+{
+    let b = s.as_bytes();
+    let mut res = Vec::new();
+    let mut i = start;
+    while i < b.len() {
+        if b[i] == b'\\' {
+            i += 2;
+            continue;
+        }
+        if b[i] == b'`' {
+            //an unclosed run is literal text, all of it
+            i = code_span_end(s, i).unwrap_or_else(||s[i..].find(|c:char|c != '`').map_or(s.len(), |p|p + i));
+            continue;
+        }
+        if seps.contains(&b[i]) {
+            res.push((i, b[i] as char));
+        }
+        i += 1;
+    }
+    res
+}
+
+//Org rules start with `|-`, Markdown delimiter cells are `---`, `:-`, `-:` or `:-:`,
+//so that data cells holding a dash as a placeholder are not taken for a rule
+fn is_rule(row :&str) -> bool
+{
+    if row.starts_with("|-") {
+        return row.chars().all(|c|matches!(c, '|' | '+' | '-' | ':') || c.is_whitespace());
+    }
+    let inner = row.trim_end().strip_prefix('|').unwrap_or(row);
+    let inner = inner.strip_suffix('|').unwrap_or(inner);
+    inner.split('|').all(|cell|{
+        let cell = cell.trim();
+        let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+        let colons = cell.len() - dashes.len();
+        !dashes.is_empty() && dashes.chars().all(|c|c == '-') && colons <= 2 && (dashes.len() >= 3 || colons > 0)
+    })
+}
+
+impl LineAnalyzer for Analyzer {
+    fn can_accept(&self, s :&str)->Result<(),AnalyzeErr>
+    {
+        let start = s.find_nwhite()?;
+        s[start..].expect_sym('|').map_err(|e|e.shift(start))
+    }
+
+    fn analyze_line<'a>(&mut self, fmt :&mut Formatter, l: &mut LineDescr<'a>)->Result<(),AnalyzeErr>
+    {
+        let start = l.s.find_nwhite()?;
+        l.s[start..].expect_sym('|').map_err(|e|e.shift(start))?;
+        let rule = is_rule(&l.s[start..]);
+        //Org rules join the cells with '+'
+        let seps : &[u8] = if rule { b"|+" } else { b"|" };
+
+        //empty column for the opening '|'
+        fmt.add_column(start, start, '|', l);
+
+        let add_cell = |fmt :&mut Formatter, begin :usize, end :usize, sep :char, l :&mut LineDescr<'a>|{
+            let cell = &l.s[begin..end];
+            let b = end - cell.trim_start().len();
+            let e = b + cell.trim().len();
+            if rule {
+                fmt.add_rule_column(b, e, sep, l);
+            }else{
+                fmt.add_column(b, e, sep, l);
+            }
+        };
+
+        let mut begin = start + 1;
+        for (pos, sep) in boundaries(l.s, begin, seps) {
+            add_cell(fmt, begin, pos, sep, l);
+            begin = pos + 1;
+        }
+        //the closing '|' is optional
+        if !l.s[begin..].trim().is_empty() {
+            add_cell(fmt, begin, l.s.len(), '|', l);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,158 @@
+use super::LineAnalyzer;
+use super::AnalyzeErr;
+use super::LineParser;
+use crate::column_tools::Formatter;
+use crate::column_tools::LineDescr;
+
+use regex::Regex;
+
+/// Rust declarations with a postfix type: struct fields (`pub name: Type,`), `const`, `static` and `let` bindings.
+/// Columns: visibility and keywords, name, type, value and a trailing `//` comment, the missing ones are empty.
+/// `::` paths and `,`, `:` or `=` inside generics, parentheses, brackets and strings don't split the type or the value.
+pub struct Analyzer {
+}
+
+struct KeyPoints
+{
+    kw_begin : usize,
+    kw_end : usize,
+    name_begin : usize,
+    name_end : usize,
+    ty : Option<(usize, usize)>,
+    value : Option<(usize, usize)>,
+    eq_pos : usize,   //position of '=', the end of the type if there is none
+    code_end : usize, //past the terminating ',' or ';'
+    comment : Option<usize>,
+}
+
+//start of a trailing `//` comment outside of string literals
+fn comment_start(s :&str, from :usize) -> Option<usize>
+{
+    let b = s.as_bytes();
+    let mut in_str = false;
+    let mut i = from;
+    while i < b.len() {
+        match b[i] {
+            b'\\' if in_str => i += 1,
+            b'"' => in_str = !in_str,
+            b'/' if !in_str && b.get(i + 1) == Some(&b'/') => return Some(i),
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+//first '=' of s[from..end] outside of brackets and strings that isn't part of `==`, `=>`, `<=`, `>=` or `!=`
+fn top_level_assign(s :&str, from :usize, end :usize) -> Option<usize>
+{
+    let b = s.as_bytes();
+    let mut depth = 0i32;
+    let mut in_str = false;
+    let mut i = from;
+    while i < end {
+        match b[i] {
+            b'\\' if in_str => i += 1,
+            b'"' => in_str = !in_str,
+            _ if in_str => {},
+            b'<' | b'(' | b'[' | b'{' => depth += 1,
+            b'>' if i > 0 && b[i - 1] == b'-' => {}, //fn(..) -> T
+            b'>' | b')' | b']' | b'}' => depth -= 1,
+            b'=' if depth == 0 => {
+                let prev = if i > from { b[i - 1] } else { b' ' };
+                let next = b.get(i + 1).copied().unwrap_or(b' ');
+                if next != b'=' && next != b'>' && !matches!(prev, b'=' | b'!' | b'<' | b'>') {
+                    return Some(i);
+                }
+            },
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+//trimmed s[begin..end], None if it is empty
+fn trimmed(s :&str, begin :usize, end :usize) -> Option<(usize, usize)>
+{
+    let part = &s[begin..end];
+    let b = end - part.trim_start().len();
+    let e = b + part.trim().len();
+    if e > b { Some((b, e)) } else { None }
+}
+
+impl Analyzer
+{
+   fn find_key_points(&self, s :&str)->Result<KeyPoints, AnalyzeErr>
+   {
+        lazy_static! {
+            static ref RE:Regex = Regex::new(r"^\s*((?:pub(?:\([^)]*\))?\s+)?(?:(?:const|static)(?:\s+mut|\s+ref)?\s+|let(?:\s+mut)?\s+)?)((?:r#)?[A-Za-z_][A-Za-z0-9_]*)").unwrap();
+        }
+        let caps = RE.captures(s).ok_or_else(||AnalyzeErr::rejected("name of a field or binding", s.find_nwhite().unwrap_or(0)))?;
+        let kw = caps.get(1).unwrap();
+        let name = caps.get(2).unwrap();
+        let binding = kw.as_str().split_whitespace().any(|w|matches!(w, "let" | "const" | "static"));
+
+        let code_end = comment_start(s, name.end()).unwrap_or(s.len());
+        let code = s[..code_end].trim_end();
+        let body_end = if code.ends_with([',', ';']) { code.len() - 1 } else { code.len() };
+        if body_end <= name.end() {
+            return Err(AnalyzeErr::missing("':' or '='", name.end()));
+        }
+
+        let after_name = s[name.end()..body_end].find_nwhite().map_err(|e|e.shift(name.end()))? + name.end();
+        let ty_begin = if s[after_name..].starts_with(':') && !s[after_name..].starts_with("::") {
+            Some(after_name + 1)
+        }else if s[after_name..].starts_with('=') && binding {
+            None
+        }else{
+            return Err(AnalyzeErr::unexpected("':'", after_name));
+        };
+
+        let eq = top_level_assign(s, ty_begin.unwrap_or(after_name), body_end);
+        let ty = match ty_begin {
+            Some(b) => Some(trimmed(s, b, eq.unwrap_or(body_end)).ok_or_else(||AnalyzeErr::missing("type after ':'", b))?),
+            None => None,
+        };
+        let value = match eq {
+            Some(e) => Some(trimmed(s, e + 1, body_end).ok_or_else(||AnalyzeErr::missing("expression after '='", e + 1))?),
+            None => None,
+        };
+        let comment = if code_end < s.len() { Some(code_end) } else { None };
+
+        Ok(KeyPoints{kw_begin : kw.start(), kw_end : kw.as_str().trim_end().len() + kw.start(), name_begin : name.start(), name_end : name.end(),
+            ty, value, eq_pos : eq.unwrap_or(body_end), code_end : code.len(), comment})
+   }
+}
+
+impl LineAnalyzer for Analyzer {
+
+    fn can_accept(&self, s :&str)->Result<(),AnalyzeErr>
+    {
+        self.find_key_points(s)?;
+        Ok(())
+    }
+
+    fn analyze_line<'a>(&mut self, fmt :&mut Formatter, l: &mut LineDescr<'a>)->Result<(),AnalyzeErr>
+    {
+        let KeyPoints{kw_begin, kw_end, name_begin, name_end, ty, value, eq_pos, code_end, comment} = self.find_key_points(l.s)?;
+
+        //missing keywords and types keep their (empty) columns, so that the other columns stay aligned
+        fmt.add_column(kw_begin, kw_end, if kw_end > kw_begin { ' ' } else { '\0' }, l);
+        fmt.add_column(name_begin, name_end, if ty.is_some() { ':' } else { '\0' }, l);
+        //the terminator stays with the last column
+        let last_sep = if comment.is_some() { ' ' } else { '\0' };
+        let (ty_begin, ty_end) = ty.unwrap_or((eq_pos, eq_pos));
+        match value {
+            Some((v_begin, _)) => {
+                fmt.add_column(ty_begin, ty_end, '=', l);
+                fmt.add_column(v_begin, code_end, last_sep, l);
+            },
+            None => fmt.add_column(ty_begin, code_end, last_sep, l),
+        }
+        if let Some(c) = comment {
+            fmt.add_column(c, l.s.len(), '\0', l);
+        }
+        Ok(())
+    }
+}
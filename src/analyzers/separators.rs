@@ -2,6 +2,7 @@ use crate::column_tools::*;
 use super::LineAnalyzer;
 use super::AnalyzeErr;
 
+#[derive(Clone)]
 pub struct Boundary
 {
     open : char,//char that 'opens' the block, also for simple separators
@@ -20,6 +21,12 @@ impl Boundary
         Boundary{open : o, close : Some(c), lim, lim_orig : lim}
     }
 
+    /// Opening and closing chars, they identify the boundary
+    pub fn chars(&self) -> (char, Option<char>)
+    {
+        (self.open, self.close)
+    }
+
     pub fn reset(&mut self)
     {
         self.lim = self.lim_orig;
@@ -53,10 +60,10 @@ impl std::str::FromStr for Boundary {
 
     fn from_str(s :&str) -> Result<Self, Self::Err>
     {
-        if let Some(last_num_idx) = s.find(|c|c < '0' || c > '9') {
-           let lim_orig = s[..last_num_idx].parse::<i16>()?;
+        if let Some(last_num_idx) = s.find(|c: char|!c.is_ascii_digit()) {
+           let lim_orig = s[..last_num_idx].parse::<i16>().map_err(|e|ParseErr::from(e).context("nesting level", &s[..last_num_idx], 0))?;
            let rest = &s[last_num_idx..];
-           if rest.len() > 0 {
+           if !rest.is_empty() {
             let mut chrs = rest.chars();
             let open_c = chrs.next().unwrap(); 
             let res = Boundary{open : open_c, close : chrs.next(), lim_orig, lim : lim_orig};
@@ -64,11 +71,12 @@ impl std::str::FromStr for Boundary {
            }
         }
         
-        Err(Self::Err{})
+        Err(ParseErr::new("nesting level followed by boundary chars", s))
     }
 }
 
 
+#[derive(Clone)]
 pub enum BoundType
 {
     Include,
@@ -83,7 +91,7 @@ impl std::str::FromStr for BoundType {
        match s {
            "include" => Ok(BoundType::Include),
            "exclude" => Ok(BoundType::Exclude),
-           &_ => Err(Self::Err{}),
+           &_ => Err(ParseErr::new("include or exclude", s)),
        }
     }
 }
@@ -96,6 +104,12 @@ pub struct Analyzer
     exclude : Vec<Boundary>
 }
 
+impl Default for Analyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Analyzer {
     pub fn new() -> Analyzer
     {
@@ -160,7 +174,7 @@ impl Analyzer {
     fn is_column_end(&mut self, c : char) -> bool
     {
         let mut res = false;
-        if let Result::Ok(_) = self.seps.binary_search(&c) {
+        if self.seps.binary_search(&c).is_ok() {
             res = true;
         }
 
@@ -178,7 +192,7 @@ impl Analyzer {
             return false;
         }
         let seps = if self.seps_new_column.is_empty() { &self.seps }else{ &self.seps_new_column };
-        if let Result::Ok(_) = seps.binary_search(&c) {
+        if seps.binary_search(&c).is_ok() {
             false //among separators? - no the column begin
         }else{
             true //some other symbol - yes, can be a column begin
@@ -199,7 +213,7 @@ impl Analyzer {
         let mut past_column_end = 0;
         let mut s = State::BeforeColumnBegin;
         for (off,v) in st.char_indices(){
-            if first_non_white && !v.is_ascii_whitespace() {
+            if first_non_white && !v.is_whitespace() {
                 first_non_white = false;
                 let first = &(st[off..]);
                 if fmt.check_line_start_to_ignore(first) {
@@ -253,36 +267,4 @@ impl LineAnalyzer for Analyzer
     {
         self.analyze_substr(fmt, l.s, 0, l)
     }
-
-    fn parse_args(&mut self, mut arg_it :std::slice::Iter<String>) -> Result<(), Box<dyn std::error::Error>> {
-        let mut separators : Vec<char> = vec![' '];
-        let mut new_column_separators : Vec<char> = vec![];
-
-        loop 
-        {
-            if let Some(arg) = arg_it.next() {
-               if arg == "--include" || arg == "--exclude" {
-                   if let Some(bound_str) = arg_it.next() {
-                       if let Ok(bnd) = bound_str.parse::<Boundary>() {
-                            let bt = (&arg[2..]).parse::<BoundType>()?;
-                            self.add_boundary(bnd, bt); 
-                       }
-                   }
-               }else if arg == "--seps" {
-                   if let Some(seps) = arg_it.next() {
-                       separators = seps.chars().collect();
-                   }
-               } else if arg == "--new_column_seps" {
-                   if let Some(seps) = arg_it.next() {
-                       new_column_separators = seps.chars().collect();
-                   }
-               } 
-            }else {
-                break;
-            }
-        };
-        self.set_separators(separators);
-        self.set_new_column_separators(new_column_separators);
-        Ok(())
-    }
 }
\ No newline at end of file
@@ -21,13 +21,11 @@ impl Analyzer
    {
         let semi_pos = s.sym(';')?;
         
-        if let Some(_) = s[..semi_pos].rfind(')') {
-            if !s[s.find_nwhite()?..].starts_with("decltype(") {
-                return Err(AnalyzeErr{});
-            }
+        if let Some(paren) = s[..semi_pos].rfind(')').filter(|_|!s[s.find_nwhite().unwrap_or(0)..].starts_with("decltype(")) {
+            return Err(AnalyzeErr::rejected("no ')' before ';'", paren));
         }
         let var_end = s[..semi_pos].rfind_nwhite()?;
-        let var_begin = s[..var_end].rfind_white()? + 1;
+        let var_begin = s[..=var_end].rfind_white()? + 1;
         let type_end = s[..var_begin].rfind_nwhite()?;
         let type_begin = s.find_nwhite()?;
         
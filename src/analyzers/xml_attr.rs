@@ -7,24 +7,32 @@ use crate::column_tools::LineDescr;
 pub struct Analyzer {
 }
 
+//position of the closing '>', it has to be the last non-whitespace char
+fn closing_bracket(s :&str)->Result<usize, AnalyzeErr>
+{
+    let end = s.rfind_nwhite()?;
+    match s[..=end].chars().next_back() {
+        Some('>') => Ok(end),
+        Some(c) => Err(AnalyzeErr::unexpected("'>'", end + 1 - c.len_utf8())),
+        None => Err(AnalyzeErr::missing("'>'", end)),
+    }
+}
 
 impl LineAnalyzer for Analyzer {
     fn can_accept(&self, s :&str)->Result<(),AnalyzeErr> 
     {
         let start = s.find_nwhite()?;
-        s[start..].expect_sym('<')?;
-        let end = s.rfind_nwhite()?;
-        s[end..].expect_sym('>')?;
+        s[start..].expect_sym('<').map_err(|e|e.shift(start))?;
+        closing_bracket(s)?;
         Ok(())
     }
     
     fn analyze_line<'a>(&mut self, fmt :&mut Formatter, l: &mut LineDescr<'a>)->Result<(),AnalyzeErr>
     {
         let start = l.s.find_nwhite()?;
-        l.s[start..].expect_sym('<')?;
-        let end = l.s.rfind_nwhite()?;
-        l.s[end..].expect_sym('>')?;
-        let tag_end = l.s[start + 1..end].find_white()? + 1 + start;
+        l.s[start..].expect_sym('<').map_err(|e|e.shift(start))?;
+        let end = closing_bracket(l.s)?;
+        let tag_end = l.s[start + 1..end].find_white().map_err(|e|e.shift(start + 1))? + 1 + start;
 
         let close_beg;
         if let Some(lc) = l.s[..end].chars().last() {
@@ -45,11 +53,11 @@ impl LineAnalyzer for Analyzer {
 
         while let Ok(lprop_beg) = ins[search..].find_nwhite() {
             let help_prop = search + lprop_beg;
-            let assign = ins[help_prop..].sym('=')?;
+            let assign = ins[help_prop..].sym('=').map_err(|e|e.shift(tag_end + help_prop))?;
             let help_assign = help_prop + assign;
             let lprop_end = ins[help_prop..help_assign].rfind_nwhite()?;
-            let lq_beg = ins[help_assign + 1..].sym('"')?;
-            let lq_end = ins[help_assign + 1 + lq_beg + 1..].sym('"')?;
+            let lq_beg = ins[help_assign + 1..].sym('"').map_err(|e|e.shift(tag_end + help_assign + 1))?;
+            let lq_end = ins[help_assign + 1 + lq_beg + 1..].sym('"').map_err(|e|e.shift(tag_end + help_assign + 1 + lq_beg + 1))?;
 
             let p_beg = help_prop + tag_end;
             let p_end = p_beg + lprop_end + 1;
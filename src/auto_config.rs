@@ -6,6 +6,10 @@ use crate::analyzers::var_decl::Analyzer as VarDeclAnalyzer;
 use crate::analyzers::bit_field::Analyzer as BitFieldAnalyzer;
 use crate::analyzers::cmnt_struct::Analyzer as CommentStructAnalyzer;
 use crate::analyzers::func_call::Analyzer as FuncCallAnalyzer;
+use crate::analyzers::pipe_table::Analyzer as PipeTableAnalyzer;
+use crate::analyzers::rust_decl::Analyzer as RustDeclAnalyzer;
+use crate::analyzers::match_arm::Analyzer as MatchArmAnalyzer;
+use crate::analyzers::continuation::Analyzer as ContinuationAnalyzer;
 use crate::analyzers::separators::Analyzer as SepLineAnalyzer;
 
 use crate::analyzers::LineAnalyzer;
@@ -15,11 +19,16 @@ use crate::analyzers::separators::BoundType;
 
 use crate::column_tools::Printer;
 use crate::column_tools::Formatter;
+use crate::column_tools::LineDescr;
 use crate::column_tools::Align;
 use crate::column_tools::SeparatorConfig;
+use crate::column_tools::ColumnStyle;
+
+use crate::lines::LineRange;
 
 #[derive(Debug)]
 #[derive(PartialEq)]
+#[derive(Clone)]
 pub enum AutoMode {
     SimpleSpace, //space separated columns
     SimpleComma, //comma separated, space as a non-new column symbol
@@ -31,6 +40,10 @@ pub enum AutoMode {
     Xml,
     BitField,
     CommentWithStruct, // /* xxxx */ {.....}
+    PipeTable,         // | a | b |, Markdown, Org-mode and Gherkin tables
+    RustDecl,          // pub name: Type, const NAME: Type = value;
+    MatchArm,          // Some(x) if x > 0 => body,
+    Continuation,      // #define body lines ending in \
     CLike(Option<char>, Option<char>)        //ignores "", '', ignores lines starting with //, depending on what comes first {} or () tries to format inside there
 }
 
@@ -38,7 +51,7 @@ fn auto_analyze_cpp(s :& str) -> Option<AutoMode> {
     let o;
     let c;
 
-    if let Some(nonwhite) = s.find(|c:char|!c.is_ascii_whitespace()) {
+    if let Some(nonwhite) = s.find(|c:char|!c.is_whitespace()) {
         match s[nonwhite..].chars().next().unwrap() {
             '{' => {o = Some('{'); c = Some('}')},
             '(' => {o = Some('('); c = Some(')')},
@@ -46,8 +59,7 @@ fn auto_analyze_cpp(s :& str) -> Option<AutoMode> {
                return None;
             },
         }
-    }else
-    {
+    }else{
         return None;
     }
 
@@ -59,31 +71,213 @@ fn try_accept<T:LineAnalyzer>(la : T, s :&str)->Result<(),AnalyzeErr>{
 }
 
 pub fn auto_analyze(s :& str) -> AutoMode {
-    if let Ok(_) = try_accept(XmlAttrAnalyzer{}, s) {
+    if try_accept(ContinuationAnalyzer{}, s).is_ok() {
+        AutoMode::Continuation
+    }else{
+        auto_analyze_content(s)
+    }
+}
+
+//mode of the line by its content, a trailing '\' aside
+fn auto_analyze_content(s :& str) -> AutoMode {
+    if try_accept(PipeTableAnalyzer{}, s).is_ok() {
+        AutoMode::PipeTable
+    }else if try_accept(XmlAttrAnalyzer{}, s).is_ok() {
         AutoMode::Xml
-    }else if let Ok(_) = try_accept(BitFieldAnalyzer{}, s) {
+    }else if try_accept(RustDeclAnalyzer{}, s).is_ok() {
+        AutoMode::RustDecl
+    }else if try_accept(MatchArmAnalyzer{}, s).is_ok() {
+        AutoMode::MatchArm
+    }else if try_accept(BitFieldAnalyzer{}, s).is_ok() {
        AutoMode::BitField 
-    }else if let Ok(_) = try_accept(AssignmentAnalyzer{}, s) {
+    }else if try_accept(AssignmentAnalyzer{}, s).is_ok() {
        AutoMode::SimpleAssignment 
-    }else if let Ok(_) = try_accept(FuncDeclAnalyzer{}, s) {
+    }else if try_accept(FuncDeclAnalyzer{}, s).is_ok() {
         AutoMode::FnDecl
-    }else if let Ok(_) = try_accept(AssignmentVarAnalyzer{}, s) {
+    }else if try_accept(AssignmentVarAnalyzer{}, s).is_ok() {
        AutoMode::SimpleVarAssignment 
-    }else if let Ok(_) = try_accept(CommentStructAnalyzer::new(), s) {
+    }else if try_accept(CommentStructAnalyzer::new(), s).is_ok() {
         AutoMode::CommentWithStruct 
-    }else if let Ok(_) = try_accept(VarDeclAnalyzer{}, s) {
+    }else if try_accept(VarDeclAnalyzer{}, s).is_ok() {
         AutoMode::VarDecl
-    }else if let Ok(_) = try_accept(FuncCallAnalyzer::new(), s) {
+    }else if try_accept(FuncCallAnalyzer::new(), s).is_ok() {
         AutoMode::FnCall
     }else if let Some(mode) = auto_analyze_cpp(s) {
         mode
-    }else if let Some(_) = s.find(',') {
+    }else if s.find(',').is_some() {
         AutoMode::SimpleComma
     }else {
         AutoMode::SimpleSpace
     }
 }
 
+//candidates of the multi-line detection in the order of priority, same as in auto_analyze
+fn auto_candidates() -> Vec<AutoMode> {
+    vec![
+        AutoMode::Continuation,
+        AutoMode::PipeTable,
+        AutoMode::Xml,
+        AutoMode::RustDecl,
+        AutoMode::MatchArm,
+        AutoMode::BitField,
+        AutoMode::SimpleAssignment,
+        AutoMode::FnDecl,
+        AutoMode::SimpleVarAssignment,
+        AutoMode::CommentWithStruct,
+        AutoMode::VarDecl,
+        AutoMode::FnCall,
+        AutoMode::CLike(Some('{'), Some('}')),
+        AutoMode::CLike(Some('('), Some(')')),
+        AutoMode::SimpleComma,
+        AutoMode::SimpleSpace,
+    ]
+}
+
+fn is_fallback(m :&AutoMode) -> bool {
+    matches!(m, AutoMode::SimpleComma | AutoMode::SimpleSpace)
+}
+
+fn mode_accepts(m :&AutoMode, cfg :&AutoConfigResult, s :&str) -> bool {
+    match m {
+        AutoMode::CLike(_, _) => auto_analyze_cpp(s).as_ref() == Some(m),
+        AutoMode::SimpleComma => s.contains(','),
+        AutoMode::SimpleSpace => true,
+        _ => cfg.analyzer.can_accept(s).is_ok(),
+    }
+}
+
+/// Score of an auto mode over a block of lines
+pub struct AutoScore
+{
+    pub mode : AutoMode,
+    pub total : usize,      //lines taken into account (non blank, not ignored by the mode)
+    pub accepted : usize,   //lines accepted by the analyzer
+    pub consistent : usize, //accepted lines with the column count close to the most common one
+    pub score : f32,
+}
+
+/// Scores every auto mode over all the lines, best first.
+/// Score is the ratio of accepted lines with a column count at most 1 away from the most common one
+/// (optional trailing columns like comments are fine).
+/// The generic SimpleComma/SimpleSpace accept almost anything, so for them the column count must match exactly
+/// and they get 10% off. Continuation needs at least half of the lines to end in `\`.
+/// Equal scores keep the auto_analyze priority.
+pub fn auto_rank(lines :&[&str]) -> Vec<AutoScore> {
+    let mut res : Vec<AutoScore> = auto_candidates().into_iter().map(|mode|{
+        let mut cfg = do_auto_config(mode.clone());
+        let mut total = 0;
+        let mut counts : Vec<usize> = Vec::new();
+        for l in lines.iter().filter(|l|!l.trim().is_empty()) {
+            if cfg.formatter.check_line_start_to_ignore(l.trim_start()) {
+                continue;
+            }
+            total += 1;
+            if mode_accepts(&mode, &cfg, l) {
+                let mut line = LineDescr::new(l);
+                if cfg.formatter.analyze_line(cfg.analyzer.as_mut(), &mut line).is_ok() && line.column_count() > 0 {
+                    counts.push(line.column_count());
+                }
+            }
+        }
+
+        let most_common = counts.iter().max_by_key(|c|counts.iter().filter(|x|x == c).count()).copied().unwrap_or(0);
+        let tolerance = if is_fallback(&mode) { 0 } else { 1 };
+        let consistent = counts.iter().filter(|c|c.abs_diff(most_common) <= tolerance).count();
+        let mut score = if total > 0 { consistent as f32 / total as f32 } else { 0.0 };
+        if is_fallback(&mode) {
+            score *= 0.9;
+        }
+        //a stray trailing '\' (a path, an escape) doesn't make the block continued lines
+        if mode == AutoMode::Continuation && counts.len() * 2 < total {
+            score = 0.0;
+        }
+        AutoScore{mode, total, accepted : counts.len(), consistent, score}
+    }).collect();
+
+    //stable, so equal scores stay in the priority order
+    res.sort_by(|a, b|b.score.total_cmp(&a.score));
+    res
+}
+
+/// Picks the auto mode that fits the most lines, see auto_rank. SimpleSpace if nothing fits
+pub fn auto_analyze_lines(lines :&[&str]) -> AutoMode {
+    auto_rank(lines).into_iter().find(|s|s.score > 0.0).map_or(AutoMode::SimpleSpace, |s|s.mode)
+}
+
+/// Block around the 1-based `line` that can be aligned together with it.
+/// The mode is picked for the cursor line alone, then the block grows up and down while the analyzer
+/// of the mode accepts the lines. Blank lines end the block, ignored lines (`line_start_to_ignore`) don't,
+/// but they are not included at its edges. `None` if the cursor line is blank, ignored or out of range.
+pub fn cursor_block(lines :&[&str], line :usize) -> Option<(AutoMode, LineRange)> {
+    let idx = line.checked_sub(1)?;
+    let cursor = lines.get(idx)?;
+    let mut mode = auto_analyze(cursor);
+    //continued lines come in blocks, a lone trailing '\' is part of the content
+    let continues = |i :usize|lines.get(i).is_some_and(|l|try_accept(ContinuationAnalyzer{}, l).is_ok());
+    if mode == AutoMode::Continuation && !(idx > 0 && continues(idx - 1)) && !continues(idx + 1) {
+        mode = auto_analyze_content(cursor);
+    }
+    let cfg = do_auto_config(mode.clone());
+
+    //Some(true) - accepted, Some(false) - ignored, None - ends the block
+    let fits = |l :&str| -> Option<bool> {
+        if l.trim().is_empty() {
+            None
+        }else if cfg.formatter.check_line_start_to_ignore(l.trim_start()) {
+            Some(false)
+        }else if mode_accepts(&mode, &cfg, l) {
+            Some(true)
+        }else {
+            None
+        }
+    };
+    if fits(cursor) != Some(true) {
+        return None;
+    }
+
+    let mut begin = idx;
+    let mut i = idx;
+    while i > 0 {
+        i -= 1;
+        match fits(lines[i]) {
+            Some(true) => begin = i,
+            Some(false) => {},
+            None => break,
+        }
+    }
+    let mut end = idx;
+    for (i, l) in lines.iter().enumerate().skip(idx + 1) {
+        match fits(l) {
+            Some(true) => end = i,
+            Some(false) => {},
+            None => break,
+        }
+    }
+    Some((mode, LineRange{begin : begin + 1, end : end + 1}))
+}
+
+/// Human readable ranking of the auto modes and the reason for the pick
+pub fn explain_auto(lines :&[&str]) -> String {
+    let rank = auto_rank(lines);
+    let mut res = format!("{:<4} {:<28} {:>8} {:>10} {:>6}\n", "rank", "mode", "accepted", "consistent", "score");
+    for (i, s) in rank.iter().enumerate() {
+        res.push_str(&format!("{:<4} {:<28} {:>8} {:>10} {:>6.2}\n", i + 1, format!("{:?}", s.mode), format!("{}/{}", s.accepted, s.total), format!("{}/{}", s.consistent, s.accepted), s.score));
+    }
+
+    if let Some(best) = rank.first() {
+        res.push_str(&format!("picked {:?}: {} of {} lines accepted, {} of them with a consistent column count", best.mode, best.accepted, best.total, best.consistent));
+        if is_fallback(&best.mode) {
+            res.push_str(", no specific analyzer fits better");
+        }
+        let tied : Vec<String> = rank.iter().skip(1).filter(|s|s.score == best.score).map(|s|format!("{:?}", s.mode)).collect();
+        if !tied.is_empty() {
+            res.push_str(&format!(", preferred over {} with the same score by priority", tied.join(", ")));
+        }
+        res.push('\n');
+    }
+    res
+}
+
 pub struct AutoConfigResult
 {
     pub printer : Printer,
@@ -97,6 +291,7 @@ pub fn do_auto_config(m:AutoMode)->AutoConfigResult {
     let analyzer : Box<dyn LineAnalyzer>;
     let mut non_matched_as_is = false;
     let mut sep_cfgs : Vec<SeparatorConfig> = vec![];
+    let mut column_styles : Vec<ColumnStyle> = vec![];
     let mut fmtr : Formatter = Formatter::new();
     let align = Align::Left;
     let print_fill = ' ';
@@ -156,6 +351,31 @@ pub fn do_auto_config(m:AutoMode)->AutoConfigResult {
                 //sep_cfgs.push("=: :2:center".parse::<SeparatorConfig>()?);
                 analyzer = Box::new(XmlAttrAnalyzer{});
             },
+            AutoMode::PipeTable => {
+                sep_cfgs.push(SeparatorConfig::new('|', ' ', 2, Align::Center));
+                sep_cfgs.push(SeparatorConfig::new('+', ' ', 2, Align::Center));
+                //the outer pipes get the space on the inner side only
+                column_styles.push(ColumnStyle::new(0).sep(' ', 1, Align::Left));
+                column_styles.push(ColumnStyle::new(-1).sep(' ', 1, Align::Right));
+                analyzer = Box::new(PipeTableAnalyzer{});
+            },
+            AutoMode::RustDecl => {
+                //doc comments (`///`) between the fields
+                fmtr.set_line_starts_to_ignore(vec!["//".to_string()]);
+                sep_cfgs.push(SeparatorConfig::new(':', ' ', 1, Align::Left));
+                sep_cfgs.push("=: :2:center".parse::<SeparatorConfig>().unwrap());
+                analyzer = Box::new(RustDeclAnalyzer{});
+            },
+            AutoMode::MatchArm => {
+                fmtr.set_line_starts_to_ignore(vec!["//".to_string()]);
+                analyzer = Box::new(MatchArmAnalyzer{});
+            },
+            AutoMode::Continuation => {
+                //the content is kept as is, indentation included
+                fmtr.set_add_pre_start(false);
+                sep_cfgs.push(SeparatorConfig::new('\\', ' ', 1, Align::Right));
+                analyzer = Box::new(ContinuationAnalyzer{});
+            },
             AutoMode::CommentWithStruct => {
                 fmtr.set_line_starts_to_ignore(vec!["//".to_string()]);
                 sep_cfgs.push(",: :1".parse::<SeparatorConfig>().unwrap());
@@ -189,6 +409,7 @@ pub fn do_auto_config(m:AutoMode)->AutoConfigResult {
 
     let mut printer = Printer::new(align, print_fill, print_fill_count, print_join, non_matched_as_is);
     printer.set_separator_configs(sep_cfgs);
+    printer.add_column_styles(column_styles);
 
     AutoConfigResult{printer, formatter:fmtr, analyzer}
 }
@@ -0,0 +1,158 @@
+use std::error::Error;
+
+use crate::config::Config;
+use crate::format_text;
+
+//quadratic LCS tables bigger than this are not built, the lines in between are replaced as a whole
+const LCS_LIMIT : usize = 4_000_000;
+
+#[derive(Clone, Copy)]
+#[derive(PartialEq)]
+enum Op {
+    Same(usize), //index in old
+    Del(usize),
+    Add(usize),  //index in new
+}
+
+//lines of old[begin..old_end] and new[begin..new_end] by their longest common subsequence
+fn lcs_ops(old :&[&str], new :&[&str], begin :usize, old_end :usize, new_end :usize, ops :&mut Vec<Op>)
+{
+    let (n, m) = (old_end - begin, new_end - begin);
+    if n.saturating_mul(m) > LCS_LIMIT {
+        ops.extend((begin..old_end).map(Op::Del));
+        ops.extend((begin..new_end).map(Op::Add));
+        return;
+    }
+    //len[i][j] - LCS of old[i..] and new[j..]
+    let mut len = vec![0u32; (n + 1) * (m + 1)];
+    let at = |i :usize, j :usize|i * (m + 1) + j;
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            len[at(i, j)] = if old[begin + i] == new[begin + j] { len[at(i + 1, j + 1)] + 1 } else { len[at(i + 1, j)].max(len[at(i, j + 1)]) };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old[begin + i] == new[begin + j] {
+            ops.push(Op::Same(begin + i));
+            i += 1;
+            j += 1;
+        }else if j == m || (i < n && len[at(i + 1, j)] >= len[at(i, j + 1)]) {
+            ops.push(Op::Del(begin + i));
+            i += 1;
+        }else{
+            ops.push(Op::Add(begin + j));
+            j += 1;
+        }
+    }
+}
+
+//edit script turning old into new
+fn edit_script(old :&[&str], new :&[&str]) -> Vec<Op>
+{
+    let mut ops : Vec<Op> = Vec::with_capacity(old.len().max(new.len()));
+    if old.len() == new.len() {
+        //formatting keeps the lines unless it wraps, a changed line is removed and added
+        for (i, (o, n)) in old.iter().zip(new.iter()).enumerate() {
+            if o == n {
+                ops.push(Op::Same(i));
+            }else{
+                ops.push(Op::Del(i));
+                ops.push(Op::Add(i));
+            }
+        }
+        return ops;
+    }
+    let prefix = old.iter().zip(new.iter()).take_while(|(o, n)|o == n).count();
+    let suffix = old[prefix..].iter().rev().zip(new[prefix..].iter().rev()).take_while(|(o, n)|o == n).count();
+    ops.extend((0..prefix).map(Op::Same));
+    lcs_ops(old, new, prefix, old.len() - suffix, new.len() - suffix, &mut ops);
+    ops.extend((old.len() - suffix..old.len()).map(Op::Same));
+    ops
+}
+
+//start line and length of one side of a hunk header, an empty side points at the line before
+fn hunk_range(start :usize, len :usize) -> String
+{
+    format!("{},{}", if len > 0 { start + 1 } else { start }, len)
+}
+
+/// Unified diff of `old` and `new` with `context` unchanged lines around the changes.
+/// Lines are compared pairwise if their count is the same (a changed line is shown as removed and added),
+/// otherwise (wrapping added lines) by their longest common subsequence.
+pub fn unified_diff(name :&str, old :&[&str], new :&[&str], context :usize) -> String
+{
+    let ops = edit_script(old, new);
+    let changed : Vec<usize> = (0..ops.len()).filter(|&i|!matches!(ops[i], Op::Same(_))).collect();
+    if changed.is_empty() {
+        return String::new();
+    }
+
+    //changes closer than two contexts share one hunk
+    let mut hunks : Vec<(usize, usize)> = Vec::new();
+    for &i in changed.iter() {
+        let begin = i.saturating_sub(context);
+        let end = (i + 1 + context).min(ops.len());
+        match hunks.last_mut() {
+            Some(h) if begin <= h.1 => h.1 = end,
+            _ => hunks.push((begin, end)),
+        }
+    }
+
+    //line positions in old and new before every op
+    let mut pos : Vec<(usize, usize)> = Vec::with_capacity(ops.len() + 1);
+    let (mut o, mut n) = (0, 0);
+    for op in ops.iter() {
+        pos.push((o, n));
+        match op {
+            Op::Same(_) => { o += 1; n += 1; },
+            Op::Del(_) => o += 1,
+            Op::Add(_) => n += 1,
+        }
+    }
+    pos.push((o, n));
+
+    let mut res = format!("--- {}\n+++ {}\n", name, name);
+    for (begin, end) in hunks {
+        let (o_begin, n_begin) = pos[begin];
+        let (o_end, n_end) = pos[end];
+        res += &format!("@@ -{} +{} @@\n", hunk_range(o_begin, o_end - o_begin), hunk_range(n_begin, n_end - n_begin));
+        let mut i = begin;
+        while i < end {
+            if let Op::Same(j) = ops[i] {
+                res += &format!(" {}\n", old[j]);
+                i += 1;
+                continue;
+            }
+            //removed lines of the run first, then the added ones
+            let run_end = (i..end).find(|&k|matches!(ops[k], Op::Same(_))).unwrap_or(end);
+            ops[i..run_end].iter().for_each(|op|if let Op::Del(j) = op { res += &format!("-{}\n", old[*j]); });
+            ops[i..run_end].iter().for_each(|op|if let Op::Add(j) = op { res += &format!("+{}\n", new[*j]); });
+            i = run_end;
+        }
+    }
+    res
+}
+
+/// Formats `input` and compares the result with it.
+/// Returns the diff of the lines formatting would change, `None` if the input is already aligned.
+pub fn check_text(name :&str, input :&str, cfg :&Config) -> Result<Option<String>, Box<dyn Error>>
+{
+    let formatted = format_text(input, cfg)?;
+    let old : Vec<&str> = input.lines().collect();
+    //formatted lines are joined with '\n', blank last lines would be lost by `lines()`.
+    //Wrapping may add lines
+    let new : Vec<&str> = if old.is_empty() { Vec::new() } else { formatted.split('\n').collect() };
+    let diff = unified_diff(name, &old, &new, 3);
+    Ok(if diff.is_empty() { None } else { Some(diff) })
+}
+
+/// Compares `input` with its partly formatted version, e.g. from `format_line_ranges`.
+/// Returns the diff, `None` if formatting changed nothing.
+pub fn check_formatted(name :&str, input :&str, formatted :&str) -> Option<String>
+{
+    let old : Vec<&str> = input.lines().collect();
+    let new : Vec<&str> = formatted.lines().collect();
+    let diff = unified_diff(name, &old, &new, 3);
+    if diff.is_empty() { None } else { Some(diff) }
+}
@@ -1,4 +1,43 @@
+use std::collections::BTreeMap;
+
 use crate::analyzers::LineAnalyzer;
+use crate::analyzers::AnalyzeErr;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Width of the string in terminal cells.
+/// Combining marks take no space, East Asian wide chars take 2 cells
+/// and a grapheme cluster (e.g. an emoji ZWJ sequence) never takes more than 2.
+pub fn display_width(s :&str)->usize
+{
+    if s.is_ascii() {
+        return s.len();
+    }
+    s.graphemes(true).map(|g|{
+        let w = g.width();
+        if w > 2 && g.chars().nth(1).is_some() { 2 } else { w }
+    }).sum()
+}
+
+/// Longest prefix of `s` that fits in `w` cells with an ellipsis appended
+pub fn truncate_to_width(s :&str, w :usize)->String
+{
+    let mut res = String::with_capacity(s.len());
+    let mut width = 0;
+    for g in s.graphemes(true) {
+        let gw = display_width(g);
+        if width + gw + 1 > w {
+            break;
+        }
+        width += gw;
+        res.push_str(g);
+    }
+    if w > 0 {
+        res.push('…');
+    }
+    res
+}
+
 /****************************************************
  * AddToString trait
  * 
@@ -41,7 +80,7 @@ impl AddToString for char
     }
     
     fn own_size(&self)->usize {
-        std::mem::size_of::<char>()
+        self.len_utf8()
     }
 }
 
@@ -50,40 +89,91 @@ pub fn write_lines_into(lines :&Vec<LineDescr>, printer :&Printer, out :&mut dyn
     let mut first_line = true;
     for l in lines.iter()
     {
-        if let Some(s) = printer.format_line(&l) {
+        if let Some(s) = printer.format_line(l) {
             if !first_line {
-                out.write("\n".as_bytes())?;
+                out.write_all("\n".as_bytes())?;
             }else{
                 first_line = false;
             }
-            out.write(s.as_bytes())?;
+            out.write_all(s.as_bytes())?;
         }
     }
     out.flush()?;
     Ok(())
 }
 
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub enum ParseErrKind {
+    Missing,       //value or part of it is empty
+    Invalid,       //value isn't one of the accepted ones
+    InvalidNumber,
+}
+
+/// Error of parsing an option value: what was expected, what was found and where
 #[derive(Debug)]
 pub struct ParseErr{
-    
+    pub kind : ParseErrKind,
+    pub expected : String,
+    pub found : Option<String>,
+    pub offset : Option<usize>, //byte offset in the parsed value
+    pub source : Option<Box<dyn std::error::Error + Send + Sync>>,
+}
+
+impl ParseErr {
+    pub fn new(expected : &str, found : &str)->Self
+    {
+        let kind = if found.is_empty() { ParseErrKind::Missing } else { ParseErrKind::Invalid };
+        Self{kind, expected : expected.to_string(), found : Some(found.to_string()), offset : None, source : None}
+    }
+
+    pub fn at(mut self, offset : usize)->Self
+    {
+        self.offset = Some(offset);
+        self
+    }
+
+    //replaces the generic description of a wrapped error with the specific one
+    pub fn context(mut self, expected : &str, found : &str, offset : usize)->Self
+    {
+        self.expected = expected.to_string();
+        self.found = Some(found.to_string());
+        self.offset = Some(offset);
+        self
+    }
 }
 
 impl std::fmt::Display for ParseErr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "parse error")
+        write!(f, "expected {}", self.expected)?;
+        if let Some(found) = &self.found {
+            write!(f, ", found '{}'", found)?;
+        }
+        if let Some(o) = self.offset {
+            write!(f, " at {}", o)?;
+        }
+        if let Some(src) = &self.source {
+            write!(f, " ({})", src)?;
+        }
+        Ok(())
     }
 }
 
 impl std::error::Error for ParseErr {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.as_ref().map(|e|e.as_ref() as &(dyn std::error::Error + 'static))
+    }
 }
 
 impl std::convert::From<std::num::ParseIntError> for ParseErr{
-    fn from(_err : std::num::ParseIntError) -> Self
+    fn from(err : std::num::ParseIntError) -> Self
     {
-        Self{}
+        Self{kind : ParseErrKind::InvalidNumber, expected : "number".to_string(), found : None, offset : None, source : Some(Box::new(err))}
     }
 }
 
+#[derive(Clone)]
 pub enum Align {
     Left,
     Center,
@@ -99,7 +189,7 @@ impl std::str::FromStr for Align {
            "left" => Ok(Align::Left),
            "right" => Ok(Align::Right),
            "center" => Ok(Align::Center),
-           &_ => Err(Self::Err{}),
+           &_ => Err(ParseErr::new("left, right or center", s)),
        }
     }
 }
@@ -126,6 +216,116 @@ where T:AddToString
    res
 }
 
+//amount of fill that align_string puts before the target
+fn leading_fill(delta :usize, a : &Align)->usize
+{
+    match a {
+        Align::Left => 0,
+        Align::Center => delta / 2,
+        Align::Right => delta,
+    }
+}
+
+
+/// Width of the string with tabs expanded to the next multiple of `tab_width`,
+/// counting from the beginning of the string. With `tab_width` 0 a tab is just 1 cell wide.
+pub fn expanded_width(s :&str, tab_width :usize)->usize
+{
+    advance_pos(0, s, tab_width)
+}
+
+fn advance_pos(pos :usize, s :&str, tab_width :usize)->usize
+{
+    if tab_width == 0 || !s.contains('\t') {
+        return pos + display_width(s);
+    }
+    s.split('\t').enumerate().fold(pos, |p, (i, part)|{
+        let p = if i > 0 { next_tab_stop(p, tab_width) } else { p };
+        p + display_width(part)
+    })
+}
+
+fn next_tab_stop(pos :usize, tab_width :usize)->usize
+{
+    (pos / tab_width + 1) * tab_width
+}
+
+fn expand_tabs(s :&str, tab_width :usize)->String
+{
+    let mut res = String::with_capacity(s.len());
+    for (i, part) in s.split('\t').enumerate() {
+        if i > 0 {
+            let pos = expanded_width(&res, tab_width);
+            res.push_str(&" ".repeat(next_tab_stop(pos, tab_width) - pos));
+        }
+        res.push_str(part);
+    }
+    res
+}
+
+/// Splits a numeric cell into the part before the decimal separator (or the exponent)
+/// and the rest, returns their widths or None if the cell isn't a number.
+/// Accepts a sign, hex literals and trailing unit/suffix letters like `12.5f`, `10ms` or `3ULL`.
+pub fn split_number(s :&str, dec_sep :char)->Option<(usize, usize)>
+{
+    let b = s.as_bytes();
+    let mut i = 0;
+    if i < b.len() && (b[i] == b'+' || b[i] == b'-') {
+        i += 1;
+    }
+    if b.len() > i + 2 && b[i] == b'0' && (b[i + 1] == b'x' || b[i + 1] == b'X') && b[i + 2].is_ascii_hexdigit() {
+        let digits = s[i + 2..].find(|c:char|!c.is_ascii_hexdigit()).map_or(s.len(), |p|p + i + 2);
+        return if is_number_suffix(&s[digits..]) { Some((display_width(s), 0)) } else { None };
+    }
+
+    let int_end = s[i..].find(|c:char|!c.is_ascii_digit()).map_or(s.len(), |p|p + i);
+    let mut digits = int_end - i;
+    let mut rest = int_end;
+    if s[rest..].starts_with(dec_sep) {
+        rest += dec_sep.len_utf8();
+        let frac_end = s[rest..].find(|c:char|!c.is_ascii_digit()).map_or(s.len(), |p|p + rest);
+        digits += frac_end - rest;
+        rest = frac_end;
+    }
+    if digits == 0 {
+        return None;
+    }
+    if s[rest..].starts_with(['e', 'E']) {
+        let mut exp = rest + 1;
+        if s[exp..].starts_with(['+', '-']) {
+            exp += 1;
+        }
+        let exp_end = s[exp..].find(|c:char|!c.is_ascii_digit()).map_or(s.len(), |p|p + exp);
+        if exp_end > exp {
+            rest = exp_end;
+        }
+    }
+    if !is_number_suffix(&s[rest..]) {
+        return None;
+    }
+    Some((display_width(&s[..int_end]), display_width(&s[int_end..])))
+}
+
+fn is_number_suffix(s :&str)->bool
+{
+    s.chars().all(|c|c.is_ascii_alphabetic() || c == '%' || c == ';' || c == ',')
+}
+
+#[derive(Clone)]
+struct NumericWidths
+{
+    all_numeric : bool,
+    int_part : usize,
+    frac_part : usize,
+}
+
+impl NumericWidths
+{
+    fn is_numeric(&self)->bool
+    {
+        self.all_numeric && self.int_part + self.frac_part > 0
+    }
+}
 
 struct Column<'a>
 {
@@ -136,29 +336,113 @@ struct Column<'a>
 pub struct LineDescr<'a>
 {
     pub s : &'a str,
-    columns : Vec<Column<'a>>
+    columns : Vec<Column<'a>>,
+    rule : bool,      //table rule, the cells are regenerated from the column widths
+    continues : bool, //the line ends inside a cell going on on the next line
 }
 
 impl<'a> LineDescr<'a>{
     pub fn new(s : &'a str) -> LineDescr<'a>
     {
-        LineDescr{s, columns : Vec::new()}
+        LineDescr{s, columns : Vec::new(), rule : false, continues : false}
+    }
+
+    /// The line ends inside a cell that goes on on the next line, like an open quoted CSV field:
+    /// its trailing whitespace is data and is kept
+    pub fn set_continues(&mut self)
+    {
+        self.continues = true;
+    }
+
+    pub fn continues(&self) -> bool
+    {
+        self.continues
+    }
+
+    /// Number of columns found by the analyzer, 0 if the line wasn't accepted
+    pub fn column_count(&self) -> usize
+    {
+        self.columns.len()
     }
 }
 
+/// What the pre-start column (the indentation) of the aligned lines becomes
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub enum IndentPolicy {
+    Max,          //every line is indented as deep as the deepest one
+    Keep,         //every line keeps its own indentation
+    First,        //indentation of the first line of the block
+    Min,          //the least indentation of the block
+    Fixed(usize), //given amount of spaces
+}
+
+impl std::str::FromStr for IndentPolicy {
+    type Err = ParseErr;
+
+    fn from_str(s :&str) -> Result<IndentPolicy, Self::Err>
+    {
+       match s.to_lowercase().as_str() {
+           "max" => Ok(IndentPolicy::Max),
+           "keep" => Ok(IndentPolicy::Keep),
+           "first" => Ok(IndentPolicy::First),
+           "min" => Ok(IndentPolicy::Min),
+           &_ => s.parse::<usize>().map(IndentPolicy::Fixed).map_err(|_|ParseErr::new("max, keep, first, min or a number", s)),
+       }
+    }
+}
+
+//cells of table rules widen their column only to this
+const RULE_MIN_WIDTH : usize = 3;
+
+//rule cell `w` wide, keeping the alignment colons of `s`
+fn rule_cell(s :&str, w :usize) -> String
+{
+    let left = s.starts_with(':');
+    let right = s.len() > 1 && s.ends_with(':');
+    let dashes = w.saturating_sub(left as usize + right as usize).max(1);
+    format!("{}{}{}", if left { ":" } else { "" }, "-".repeat(dashes), if right { ":" } else { "" })
+}
+
+//leading whitespace and the rest of a pre-start column
+fn split_indent(s :&str) -> (&str, &str)
+{
+    s.split_at(s.find(|c:char|!c.is_whitespace()).unwrap_or(s.len()))
+}
+
+#[derive(Clone)]
 pub struct Formatter
 {
     columns : Vec<usize>,
+    numeric : Vec<NumericWidths>,
+    decimal_sep : char,
+    tab_width : usize,
     total_size : usize,
     line_starts_to_ignore : Vec<String>,
     add_pre_start : bool,
+    indent_policy : IndentPolicy,
+    block_indent : Option<String>, //indentation all lines get, except for Max and Keep
+    col_seps : Vec<char>,          //separator after each column, to estimate the line width
+    max_column_width : Option<usize>,
+    clip_outliers : bool,
+    outlier_percent : Option<usize>,
+    cell_widths : Vec<BTreeMap<usize, usize>>, //cells per width of each column, only kept for outlier_percent
+    column_aligns : Vec<Option<Align>>, //alignment set by the table rule
+}
+
+impl Default for Formatter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Formatter
 {
     pub fn new()->Self
     {
-        Self{columns:Vec::new(), total_size: 0, line_starts_to_ignore : Vec::new(), add_pre_start : false}
+        Self{columns:Vec::new(), numeric:Vec::new(), decimal_sep:'.', tab_width: 0, total_size: 0, line_starts_to_ignore : Vec::new(), add_pre_start : false, indent_policy : IndentPolicy::Max, block_indent : None,
+            col_seps : Vec::new(), max_column_width : None, clip_outliers : false, outlier_percent : None, cell_widths : Vec::new(), column_aligns : Vec::new()}
     }
 
     pub fn clear(&mut self)
@@ -176,76 +460,259 @@ impl Formatter
         self.line_starts_to_ignore = vals;
     }
 
+    pub fn set_decimal_separator(&mut self, sep : char) {
+        self.decimal_sep = sep;
+    }
+
+    /// Tabs are expanded to multiples of `tab_width` when measuring, 0 counts a tab as 1 cell
+    pub fn set_tab_width(&mut self, tab_width : usize) {
+        self.tab_width = tab_width;
+    }
+
+    pub fn tab_width(&self) -> usize {
+        self.tab_width
+    }
+
+    /// With a policy other than Max only the part of the pre-start column after the indentation is aligned
+    pub fn set_indent_policy(&mut self, policy : IndentPolicy) {
+        self.indent_policy = policy;
+    }
+
+    /// Cells wider than `max` don't widen their column, the printer lets them stick out.
+    /// With `clip` they count as `max` wide, for the printer to truncate them to it. 0 is no limit
+    pub fn set_max_column_width(&mut self, max : Option<usize>, clip : bool) {
+        self.max_column_width = max.filter(|m|*m > 0);
+        self.clip_outliers = clip;
+    }
+
+    /// The widest `percent` % of the cells of each column don't widen it
+    pub fn set_outlier_percent(&mut self, percent : Option<usize>) {
+        self.outlier_percent = percent.map(|p|p.min(100));
+    }
+
+    /// Forgets the collected column widths, keeps the settings
+    pub fn reset_columns(&mut self)
+    {
+        self.columns.clear();
+        self.numeric.clear();
+        self.total_size = 0;
+        self.block_indent = None;
+        self.col_seps.clear();
+        self.cell_widths.clear();
+        self.column_aligns.clear();
+    }
+
+    /// Widths of the integer and the fraction parts if all the cells of the column are numbers
+    pub fn numeric_column(&self, idx : usize) -> Option<(usize, usize)>
+    {
+        self.numeric.get(idx).filter(|n|n.is_numeric()).map(|n|(n.int_part, n.frac_part))
+    }
+
+    /// Alignment of the column given by the colons of a table rule
+    pub fn column_align(&self, idx : usize) -> Option<&Align>
+    {
+        self.column_aligns.get(idx).and_then(|a|a.as_ref())
+    }
+
     fn check_biggest_column(&mut self, idx : usize, sz :usize)
     {
         if self.columns.len() <= idx {
             self.columns.resize(idx + 1, 0);
+            self.numeric.resize(idx + 1, NumericWidths{all_numeric : true, int_part : 0, frac_part : 0});
         }
         if self.columns[idx] < sz {
             self.columns[idx] = sz;
         }
     }
 
+    fn account_pre_start(&mut self, pre :&str)
+    {
+        let tw = self.tab_width;
+        if self.indent_policy == IndentPolicy::Max {
+            self.check_biggest_column(0, expanded_width(pre, tw));
+            return;
+        }
+
+        let (indent, rest) = split_indent(pre);
+        self.check_biggest_column(0, expanded_width(rest, tw));
+        match &self.indent_policy {
+            IndentPolicy::First => if self.block_indent.is_none() {
+                self.block_indent = Some(indent.to_string());
+            },
+            IndentPolicy::Min => {
+                let narrower = match &self.block_indent {
+                    Some(b) => expanded_width(b, tw) > expanded_width(indent, tw),
+                    None => true,
+                };
+                if narrower {
+                    self.block_indent = Some(indent.to_string());
+                }
+            },
+            IndentPolicy::Fixed(n) => self.block_indent = Some(" ".repeat(*n)),
+            IndentPolicy::Max | IndentPolicy::Keep => {},
+        }
+    }
+
     pub fn add_column<'b>(&mut self, begin:usize, end:usize, ch : char, l : &mut LineDescr<'b>)
+    {
+        let cnt = expanded_width(&l.s[begin..end], self.tab_width);
+        self.push_column(begin, end, ch, l, cnt, true);
+    }
+
+    /// Adds a cell of a header row: it widens the column, but doesn't keep it from being numeric
+    pub fn add_header_column<'b>(&mut self, begin:usize, end:usize, ch : char, l : &mut LineDescr<'b>)
+    {
+        let cnt = expanded_width(&l.s[begin..end], self.tab_width);
+        self.push_column(begin, end, ch, l, cnt, false);
+    }
+
+    /// Adds the last cell of a line that goes on on the next line, like the opening part of a multi-line quoted field.
+    /// It doesn't widen the column and is printed as is
+    pub fn add_tail_column<'b>(&mut self, begin:usize, end:usize, ch : char, l : &mut LineDescr<'b>)
+    {
+        l.continues = true;
+        self.push_column(begin, end, ch, l, 0, false);
+    }
+
+    /// Adds a cell of a table rule like `:---:`. It widens the column only to the minimal rule width,
+    /// the printer draws it as wide as the column. Its colons set the alignment of the column
+    pub fn add_rule_column<'b>(&mut self, begin:usize, end:usize, ch : char, l : &mut LineDescr<'b>)
+    {
+        let cell = &l.s[begin..end];
+        let align = match (cell.starts_with(':'), cell.len() > 1 && cell.ends_with(':')) {
+            (true, true) => Some(Align::Center),
+            (true, false) => Some(Align::Left),
+            (false, true) => Some(Align::Right),
+            (false, false) => None,
+        };
+        l.rule = true;
+        self.push_column(begin, end, ch, l, RULE_MIN_WIDTH, false);
+        if align.is_some() {
+            let idx = l.columns.len() - 1;
+            if self.column_aligns.len() <= idx {
+                self.column_aligns.resize(idx + 1, None);
+            }
+            self.column_aligns[idx] = align;
+        }
+    }
+
+    fn push_column<'b>(&mut self, begin:usize, end:usize, ch : char, l : &mut LineDescr<'b>, cnt : usize, numeric : bool)
     {
         if self.add_pre_start && l.columns.is_empty() {
-            l.columns.push(Column{col : &l.s[..], sep : '\0'});
+            l.columns.push(Column{col : l.s, sep : '\0'});
             self.check_biggest_column(0, 0);
         }
         
-        let cnt = end - begin;
         l.columns.push(Column{col : &l.s[begin..end], sep : ch});
-        self.check_biggest_column(l.columns.len() - 1, cnt);
+        let idx = l.columns.len() - 1;
+        //outliers are measured in finish
+        let outlier = self.max_column_width.is_some_and(|m|cnt > m);
+        let counted = match self.max_column_width {
+            Some(m) if outlier && self.clip_outliers => m,
+            _ if outlier || self.outlier_percent.is_some() => 0,
+            _ => cnt,
+        };
+        self.check_biggest_column(idx, counted);
+        if self.col_seps.len() <= idx {
+            self.col_seps.resize(idx + 1, '\0');
+        }
+        if self.col_seps[idx] == '\0' {
+            self.col_seps[idx] = ch;
+        }
+        if self.outlier_percent.is_some() && !outlier && !l.continues {
+            if self.cell_widths.len() <= idx {
+                self.cell_widths.resize(idx + 1, BTreeMap::new());
+            }
+            *self.cell_widths[idx].entry(cnt).or_insert(0) += 1;
+        }
+
+        let num = &mut self.numeric[idx];
+        if numeric && num.all_numeric {
+            if let Some((int_part, frac_part)) = split_number(&l.s[begin..end], self.decimal_sep) {
+                if !outlier {
+                    num.int_part = num.int_part.max(int_part);
+                    num.frac_part = num.frac_part.max(frac_part);
+                }
+            }else{
+                num.all_numeric = false;
+            }
+        }
     }
 
     pub fn finish(&mut self)
     {
+        if let Some(p) = self.outlier_percent {
+            for (idx, widths) in self.cell_widths.iter().enumerate() {
+                let cells : usize = widths.values().sum();
+                if cells == 0 {
+                    continue;
+                }
+                //width of the keep-th narrowest cell
+                let keep = (cells * (100 - p)).div_ceil(100).max(1);
+                let mut seen = 0;
+                let w = widths.iter().find(|(_, n)|{ seen += **n; seen >= keep }).map_or(0, |(w, _)|*w);
+                self.columns[idx] = self.columns[idx].max(w);
+            }
+        }
+        self.total_size = self.columns.iter().sum();
+    }
+
+    //caps the real columns so that the sum of the widths and the separators fits in `max_line`
+    fn fit_line_width(&mut self, max_line : usize, sep_width : impl Fn(char)->usize)
+    {
+        let first_real = if self.add_pre_start { 1 } else { 0 };
+        if self.columns.len() <= first_real {
+            return;
+        }
+        let overhead : usize = self.columns[..first_real].iter().sum::<usize>()
+            + self.col_seps.iter().filter(|c|**c != '\0').map(|c|sep_width(*c)).sum::<usize>();
+        let avail = max_line.saturating_sub(overhead);
+        let real = &mut self.columns[first_real..];
+        if real.iter().sum::<usize>() <= avail {
+            return;
+        }
+        //largest cap the columns fit with
+        let mut cap = real.iter().copied().max().unwrap_or(0);
+        while cap > 0 && real.iter().map(|w|(*w).min(cap)).sum::<usize>() > avail {
+            cap -= 1;
+        }
+        real.iter_mut().for_each(|w|*w = (*w).min(cap));
         self.total_size = self.columns.iter().sum();
-        
     }
 
     pub fn check_line_start_to_ignore(&self, l: &str) -> bool{
         self.line_starts_to_ignore.iter().any(|s|l.starts_with(s))
     }
 
-    pub fn analyze_line<'a>(&mut self, analyzer :&mut dyn LineAnalyzer, l: &mut LineDescr<'a>)
+    /// Analyzes the line and accounts its columns, a rejected line gets no columns
+    pub fn analyze_line<'a>(&mut self, analyzer :&mut dyn LineAnalyzer, l: &mut LineDescr<'a>) -> Result<(), AnalyzeErr>
     {
-        if let Ok(_) = analyzer.analyze_line(self, l){
-            if !l.columns.is_empty() && self.add_pre_start {
-                let ps = l.s.as_ptr();
-                let pf = l.columns[1].col.as_ptr();
-                let first = pf as usize - ps as usize;
-                
-                l.columns[0] = Column{col : &l.s[..first], sep : '\0'};
-                if self.columns[0] < first {
-                    self.columns[0] = first;
+        match analyzer.analyze_line(self, l) {
+            Ok(()) => {
+                if !l.columns.is_empty() && self.add_pre_start {
+                    let ps = l.s.as_ptr();
+                    let pf = l.columns[1].col.as_ptr();
+                    let first = pf as usize - ps as usize;
+                    
+                    l.columns[0] = Column{col : &l.s[..first], sep : '\0'};
+                    self.account_pre_start(&l.s[..first]);
                 }
-            }
-        }else
-        {
-            l.columns.clear();
-        }
-    }
-
-    pub fn parse_args(&mut self, mut arg_it :std::slice::Iter<String>) -> Result<(), Box<dyn std::error::Error>> {
-        loop 
-        {
-            if let Some(arg) = arg_it.next() {
-               if arg == "--line_start_to_ignore" {
-                   if let Some(ignore) = arg_it.next() {
-                        self.line_starts_to_ignore.push(ignore.clone());
-                   }
-               }else if arg == "--prestart" {
-                   self.add_pre_start = true;
-               }
-            }else {
-                break;
+                Ok(())
+            },
+            Err(e) => {
+                l.columns.clear();
+                Err(e)
             }
         }
-        Ok(())
+    }
+
+    pub fn column_count(&self) -> usize
+    {
+        self.columns.len()
     }
 }
 
+#[derive(Clone)]
 pub struct SeparatorConfig
 {
     sep : char,
@@ -261,6 +728,11 @@ impl SeparatorConfig {
        let sep_str = align_string(sep, &fill.to_string(), count as usize, &align);
        Self{sep, fill, count, align, sep_str}
    }
+
+   pub fn sep(&self)->char
+   {
+       self.sep
+   }
 }
 
 impl std::str::FromStr for SeparatorConfig {
@@ -269,25 +741,219 @@ impl std::str::FromStr for SeparatorConfig {
     fn from_str(s :&str) -> Result<Self, Self::Err>
     {
         let mut it = s.split(':');
+        let mut pos = 0;
         if let Some(sep) = it.next() {
             
-            if sep.len() == 1 {
+            if sep.chars().count() == 1 {
                let mut res = SeparatorConfig{sep : sep.chars().next().unwrap(), fill : ' ', count : 1, align : Align::Left, sep_str: String::new() }; 
+               pos += sep.len() + 1;
                if let Some(f) = it.next() {
-                   res.fill = f.chars().next().unwrap();
+                   res.fill = f.chars().next().ok_or_else(||ParseErr::new("fill char", f).at(pos))?;
+                   pos += f.len() + 1;
                }
                if let Some(c) = it.next() {
-                   res.count = c.parse::<u8>()?;
+                   res.count = c.parse::<u8>().map_err(|e|ParseErr::from(e).context("separator count", c, pos))?;
+                   pos += c.len() + 1;
                }
                if let Some(a) = it.next() {
-                   res.align = a.parse::<Align>()?;
+                   res.align = a.parse::<Align>().map_err(|e|e.at(pos))?;
                }
                res.sep_str = align_string(res.sep, &res.fill.to_string(), res.count as usize, &res.align);
                return Ok(res);
             }
+            return Err(ParseErr::new("single separator char", sep).at(0));
         }
         
-        Err(Self::Err{})
+        Err(ParseErr::new("separator char", s))
+    }
+}
+
+/// Per column overrides of the printer settings, parsed from `IDX:key=val,key=val`.
+/// IDX is 0-based and doesn't count the pre-start column, negative IDX counts from the end of the line (-1 is the last column).
+/// Keys: `align`, `fill`, `min` (minimal width) and `sep_fill`, `sep_count`, `sep_align`
+/// for the separator following the column.
+#[derive(Clone)]
+pub struct ColumnStyle
+{
+    idx : isize,
+    align : Option<Align>,
+    fill : Option<char>,
+    min : Option<usize>,
+    sep_fill : Option<char>,
+    sep_count : Option<u8>,
+    sep_align : Option<Align>,
+}
+
+impl ColumnStyle {
+    pub fn new(idx : isize)->Self
+    {
+        Self{idx, align : None, fill : None, min : None, sep_fill : None, sep_count : None, sep_align : None}
+    }
+
+    pub fn align(mut self, a : Align)->Self { self.align = Some(a); self }
+    pub fn fill(mut self, f : char)->Self { self.fill = Some(f); self }
+    pub fn min(mut self, m : usize)->Self { self.min = Some(m); self }
+    pub fn index(&self)->isize { self.idx }
+    pub fn sep(mut self, fill : char, count : u8, a : Align)->Self
+    {
+        self.sep_fill = Some(fill);
+        self.sep_count = Some(count);
+        self.sep_align = Some(a);
+        self
+    }
+
+    fn matches(&self, idx : usize, count : usize)->bool
+    {
+        if self.idx < 0 {
+            count as isize + self.idx == idx as isize
+        }else{
+            self.idx as usize == idx
+        }
+    }
+
+    //fields set by `later` replace these ones
+    fn merge(&mut self, later : &ColumnStyle)
+    {
+        self.align = later.align.clone().or(self.align.take());
+        self.fill = later.fill.or(self.fill);
+        self.min = later.min.or(self.min);
+        self.sep_fill = later.sep_fill.or(self.sep_fill);
+        self.sep_count = later.sep_count.or(self.sep_count);
+        self.sep_align = later.sep_align.clone().or(self.sep_align.take());
+    }
+
+    fn has_sep(&self)->bool
+    {
+        self.sep_fill.is_some() || self.sep_count.is_some() || self.sep_align.is_some()
+    }
+}
+
+impl std::str::FromStr for ColumnStyle {
+    type Err = ParseErr;
+
+    fn from_str(s :&str) -> Result<Self, Self::Err>
+    {
+        let (idx, rest) = s.split_once(':').unwrap_or((s, ""));
+        let mut res = ColumnStyle::new(idx.trim().parse::<isize>().map_err(|e|ParseErr::from(e).context("column index", idx, 0))?);
+        let mut pos = idx.len() + 1;
+        for kv in rest.split(',') {
+            let kv_pos = pos;
+            pos += kv.len() + 1;
+            if kv.is_empty() {
+                continue;
+            }
+            let (k, v) = kv.split_once('=').ok_or_else(||ParseErr::new("key=value", kv).at(kv_pos))?;
+            let v_pos = kv_pos + k.len() + 1;
+            let one_char = |v :&str|v.chars().next().ok_or_else(||ParseErr::new("fill char", v).at(v_pos));
+            match k.trim() {
+                "align" => res.align = Some(v.parse::<Align>().map_err(|e|e.at(v_pos))?),
+                "fill" => res.fill = Some(one_char(v)?),
+                "min" => res.min = Some(v.parse::<usize>().map_err(|e|ParseErr::from(e).context("minimal width", v, v_pos))?),
+                "sep_fill" => res.sep_fill = Some(one_char(v)?),
+                "sep_count" => res.sep_count = Some(v.parse::<u8>().map_err(|e|ParseErr::from(e).context("separator count", v, v_pos))?),
+                "sep_align" => res.sep_align = Some(v.parse::<Align>().map_err(|e|e.at(v_pos))?),
+                &_ => return Err(ParseErr::new("align, fill, min, sep_fill, sep_count or sep_align", k).at(kv_pos)),
+            }
+        }
+        Ok(res)
+    }
+}
+
+#[derive(Clone)]
+pub enum NumericAlign {
+    Off,
+    Right,   //numeric columns are right aligned
+    Decimal, //numeric columns are aligned on the decimal separator or the exponent
+}
+
+impl std::str::FromStr for NumericAlign {
+    type Err = ParseErr;
+
+    fn from_str(s :&str) -> Result<NumericAlign, Self::Err>
+    {
+       match s.to_lowercase().as_str() {
+           "off" => Ok(NumericAlign::Off),
+           "right" => Ok(NumericAlign::Right),
+           "decimal" => Ok(NumericAlign::Decimal),
+           &_ => Err(ParseErr::new("off, right or decimal", s)),
+       }
+    }
+}
+
+#[derive(Clone)]
+pub enum TabMode {
+    Spaces, //padding with the fill char, tabs inside columns are expanded
+    Smart,  //indentation with tabs, alignment with spaces
+    Stops,  //like Smart, but padding uses tabs as long as they don't pass the alignment stop
+}
+
+impl std::str::FromStr for TabMode {
+    type Err = ParseErr;
+
+    fn from_str(s :&str) -> Result<TabMode, Self::Err>
+    {
+       match s.to_lowercase().as_str() {
+           "spaces" => Ok(TabMode::Spaces),
+           "smart" => Ok(TabMode::Smart),
+           "stops" => Ok(TabMode::Stops),
+           &_ => Err(ParseErr::new("spaces, smart or stops", s)),
+       }
+    }
+}
+
+/// What happens to cells wider than their column (see the width budgets of the formatter and the printer)
+#[derive(Clone)]
+#[derive(PartialEq)]
+pub enum Overflow {
+    Realign,  //the cell sticks out, the following columns catch up with the alignment as soon as they can
+    Truncate, //the cell is cut to the column width with an ellipsis, display only
+}
+
+impl std::str::FromStr for Overflow {
+    type Err = ParseErr;
+
+    fn from_str(s :&str) -> Result<Overflow, Self::Err>
+    {
+       match s.to_lowercase().as_str() {
+           "realign" => Ok(Overflow::Realign),
+           "truncate" => Ok(Overflow::Truncate),
+           &_ => Err(ParseErr::new("realign or truncate", s)),
+       }
+    }
+}
+
+//output line that keeps track of the current display position
+struct LineBuf
+{
+    s : String,
+    pos : usize,
+    tab_width : usize,
+    tab_stops : bool,
+}
+
+impl LineBuf
+{
+    fn push_str(&mut self, s :&str)
+    {
+        self.pos = advance_pos(self.pos, s, self.tab_width);
+        self.s.push_str(s);
+    }
+
+    fn pad(&mut self, fill :&str, cnt :usize)
+    {
+        let mut target = self.pos + cnt;
+        if self.tab_stops && fill == " " {
+            while next_tab_stop(self.pos, self.tab_width) <= target {
+                self.s.push('\t');
+                self.pos = next_tab_stop(self.pos, self.tab_width);
+            }
+            target -= self.pos;
+            self.pos += target;
+            self.s.push_str(&fill.repeat(target));
+        }else{
+            self.pos = target;
+            self.s.push_str(&fill.repeat(cnt));
+        }
     }
 }
 
@@ -300,30 +966,166 @@ pub struct Printer
     fmt : Option<Formatter>,
     non_matched_as_is : bool,//lines with not exactly amount of columns will be written as is
     sep_joins : Vec<SeparatorConfig>,
+    column_styles : Vec<ColumnStyle>,
+    numeric_align : NumericAlign,
+    rectangular : bool,//pad the last column of each line too
+    tab_mode : TabMode,
+    max_line_width : Option<usize>,
+    overflow : Overflow,
+    wrap : Option<usize>, //line width the last column is word-wrapped at
+    unalign : bool,
+}
+
+impl Default for Printer {
+    fn default() -> Self
+    {
+        Self{fill : ' ', align : Align::Center, fmt:None, fill_count : 0, join : String::new(), non_matched_as_is : false, sep_joins : Vec::new(), column_styles : Vec::new(), numeric_align : NumericAlign::Off, rectangular : false, tab_mode : TabMode::Spaces, max_line_width : None, overflow : Overflow::Realign, wrap : None, unalign : false}
+    }
 }
 
 impl Printer{
     pub fn new(align:Align, fill : char, fill_count : u8, join : String, non_matched_as_is : bool) -> Self
     {
-        Self{fill, align, fmt:None, fill_count, join, non_matched_as_is, sep_joins : Vec::new()}
+        Self{fill, align, fmt:None, fill_count, join, non_matched_as_is, sep_joins : Vec::new(), column_styles : Vec::new(), numeric_align : NumericAlign::Off, rectangular : false, tab_mode : TabMode::Spaces, max_line_width : None, overflow : Overflow::Realign, wrap : None, unalign : false}
+    }
+
+    pub fn set_rectangular(&mut self, val : bool) {
+        self.rectangular = val;
+    }
+
+    /// Has effect only with a tab width set in the formatter
+    pub fn set_tab_mode(&mut self, tm : TabMode) {
+        self.tab_mode = tm;
+    }
+
+    /// Columns are narrowed evenly, widest first, so that aligned lines fit in `max` cells
+    pub fn set_max_line_width(&mut self, max : Option<usize>) {
+        self.max_line_width = max;
     }
 
-    pub fn default() -> Self
+    pub fn set_overflow(&mut self, o : Overflow) {
+        self.overflow = o;
+    }
+
+    /// The last column of lines longer than `width` is word-wrapped onto continuation lines
+    /// indented to the column start, a leading `//` or `#` comment marker is repeated on them
+    pub fn set_wrap(&mut self, width : Option<usize>) {
+        self.wrap = width;
+    }
+
+    /// Columns get no padding, only the separators (per `SeparatorConfig`) and `fill_count` are kept between them.
+    /// The pre-start column keeps the indentation of the line, table rules are drawn as short as possible
+    pub fn set_unalign(&mut self, val : bool) {
+        self.unalign = val;
+    }
+
+    //wrapped text of the last column starting at `start`, None if it fits or can't be wrapped
+    fn wrap_cell(&self, s :&str, start : usize, tab_width : usize) -> Option<String>
     {
-        Self{fill : ' ', align : Align::Center, fmt:None, fill_count : 0, join : String::new(), non_matched_as_is : false, sep_joins : Vec::new()}
+        let width = self.wrap?;
+        if start + display_width(s) <= width {
+            return None;
+        }
+        let marker_len = match s.chars().next() {
+            Some('/') if s.starts_with("//") => s.find(|c:char|c != '/').unwrap_or(s.len()),
+            Some('#') => s.find(|c:char|c != '#').unwrap_or(s.len()),
+            _ => 0,
+        };
+        let marker_end = s[marker_len..].find(|c:char|!c.is_whitespace()).map_or(s.len(), |p|p + marker_len);
+        let marker = &s[..marker_end];
+        let avail = width.checked_sub(start + display_width(marker)).filter(|a|*a > 0)?;
+
+        let mut lines : Vec<String> = Vec::new();
+        let mut cur = String::new();
+        for word in s[marker_end..].split_whitespace() {
+            if !cur.is_empty() && display_width(&cur) + 1 + display_width(word) > avail {
+                lines.push(std::mem::take(&mut cur));
+            }
+            if !cur.is_empty() {
+                cur.push(' ');
+            }
+            cur.push_str(word);
+        }
+        lines.push(cur);
+        if lines.len() < 2 {
+            return None;
+        }
+
+        let indent = if tab_width > 0 { self.indent(start, tab_width) } else { " ".repeat(start) };
+        let cont = format!("\n{}{}", indent, marker);
+        Some(format!("{}{}", marker, lines.join(&cont)))
     }
-    
-    pub fn set_formatter(&mut self, fmt :Formatter) {
+
+    //indentation of the given width
+    fn indent(&self, w : usize, tab_width : usize) -> String
+    {
+        match self.tab_mode {
+            TabMode::Smart | TabMode::Stops => "\t".repeat(w / tab_width) + &" ".repeat(w % tab_width),
+            TabMode::Spaces => " ".repeat(w),
+        }
+    }
+
+    pub fn set_numeric_align(&mut self, na : NumericAlign) {
+        self.numeric_align = na;
+    }
+
+    /// Applies the line width budget to the widths of the formatter
+    pub fn set_formatter(&mut self, mut fmt :Formatter) {
+        if let Some(max) = self.max_line_width {
+            let join = if self.join.is_empty() { None } else { Some(display_width(&self.join)) };
+            fmt.fit_line_width(max, |c|join.unwrap_or_else(||display_width(&self.sep_string(c, None))) + self.fill_count as usize);
+        }
         self.fmt = Some(fmt);
     }
 
+    pub fn take_formatter(&mut self) -> Option<Formatter> {
+        self.fmt.take()
+    }
+
     pub fn set_separator_configs(&mut self, cfgs : Vec<SeparatorConfig>) {
        self.sep_joins = cfgs; 
     }
 
     fn find_sep_config(&self, sep : char) -> Option<&SeparatorConfig>
     {
-        return self.sep_joins.iter().find(|i| i.sep == sep);
+        //the last one wins, like for column styles
+        self.sep_joins.iter().rev().find(|i| i.sep == sep)
+    }
+
+    /// Styles matching the same column are merged field by field, later styles take precedence
+    pub fn add_column_styles(&mut self, styles : Vec<ColumnStyle>) {
+        self.column_styles.extend(styles);
+    }
+
+    //all the styles matching the column merged, with a positive and a negative index alike
+    fn find_column_style(&self, idx : usize, count : usize) -> Option<ColumnStyle>
+    {
+        let mut matching = self.column_styles.iter().filter(|st| st.matches(idx, count));
+        let mut res = matching.next()?.clone();
+        matching.for_each(|st|res.merge(st));
+        Some(res)
+    }
+
+    fn sep_string(&self, sep : char, style : Option<&ColumnStyle>) -> String
+    {
+        let sep_cfg = self.find_sep_config(sep);
+        match style {
+            Some(st) if st.has_sep() => {
+                let fill = st.sep_fill.or(sep_cfg.map(|c|c.fill)).unwrap_or(' ');
+                let count = st.sep_count.or(sep_cfg.map(|c|c.count)).unwrap_or(0);
+                let align = st.sep_align.as_ref().or(sep_cfg.map(|c|&c.align)).unwrap_or(&Align::Left);
+                align_string(sep, &fill.to_string(), count as usize, align)
+            },
+            _ => match sep_cfg {
+                Some(c) => c.sep_str.clone(),
+                None => sep.to_string(),
+            }
+        }
+    }
+
+    pub fn non_matched_as_is(&self) -> bool
+    {
+        self.non_matched_as_is
     }
 
     pub fn format_line(&self, l : &LineDescr) -> Option<String>
@@ -333,12 +1135,25 @@ impl Printer{
             return Some(l.s.to_string());
         }
         
-        let mut res = String::with_capacity(fmt.total_size + fmt.columns.len() * (self.join.len() + self.fill_count as usize));
+        let tw = fmt.tab_width;
+        let mut res = LineBuf{
+            s : String::with_capacity(fmt.total_size + fmt.columns.len() * (self.join.len() + self.fill_count as usize)),
+            pos : 0,
+            tab_width : tw,
+            tab_stops : tw > 0 && matches!(self.tab_mode, TabMode::Stops),
+        };
         let fill_str = self.fill.to_string();
         let explicit_join = !self.join.is_empty();
         let mut skip_join = true;
+        let mut debt = 0; //how far an overflowing cell pushed the line past the alignment
+        let first_real = if fmt.add_pre_start { 1 } else { 0 };
+        let real_count = l.columns.len() - first_real;
+        //nothing but padding would be printed after this column
+        //Org rules are drawn with dashes up to the junctions
+        let org_rule = l.rule && l.columns.iter().any(|s|s.sep == '+');
+        let last = if self.rectangular { l.columns.len() - 1 } else { l.columns.iter().rposition(|s|!s.col.is_empty() || s.sep != '\0').unwrap_or(0) };
         
-        for (c,s) in l.columns.iter().enumerate(){
+        for (c,s) in l.columns.iter().enumerate().take(last + 1){
             if explicit_join && !skip_join {
                 res.push_str(&self.join);
             }
@@ -346,62 +1161,119 @@ impl Printer{
             if skip_join && fmt.columns[c] > 0 {
                 skip_join = false;
             }
+
+            if c < first_real && fmt.indent_policy != IndentPolicy::Max {
+                let (own, rest) = split_indent(s.col);
+                let indent = fmt.block_indent.as_deref().unwrap_or(own);
+                if tw > 0 && !matches!(self.tab_mode, TabMode::Spaces) {
+                    res.push_str(&self.indent(expanded_width(indent, tw), tw));
+                }else{
+                    res.push_str(indent);
+                }
+                res.push_str(rest);
+                let w = if self.unalign { expanded_width(rest, tw) } else { fmt.columns[c] };
+                if w > 0 {
+                    res.pad(&fill_str, w - expanded_width(rest, tw) + self.fill_count as usize);
+                }
+                continue;
+            }
+
+            if c < first_real && tw > 0 && !matches!(self.tab_mode, TabMode::Spaces) {
+                res.push_str(&self.indent(if self.unalign { expanded_width(s.col, tw) } else { fmt.columns[c] }, tw));
+                continue;
+            }
             
-            let subs : &str = s.col;
-            let w = fmt.columns[c];
-            let delta = w - subs.len() + self.fill_count as usize;
-            
-            res.push_str(&align_string(subs, &fill_str, delta, &self.align));
+            let style = if c >= first_real { self.find_column_style(c - first_real, real_count) } else { None };
+            let style = style.as_ref();
+            let expanded;
+            //a cell starting the line is at a tab stop already, its tabs are indentation unless only spaces are wanted
+            let keep_tabs = res.pos == 0 && !matches!(self.tab_mode, TabMode::Spaces);
+            let mut subs : &str = if c >= first_real && tw > 0 && s.col.contains('\t') && !keep_tabs {
+                expanded = expand_tabs(s.col, tw);
+                &expanded
+            }else{
+                s.col
+            };
+            let mut w = if self.unalign { 0 } else { style.map_or(fmt.columns[c], |st|fmt.columns[c].max(st.min.unwrap_or(0))) };
+            let rule;
+            if l.rule && c >= first_real && !s.col.is_empty() {
+                rule = rule_cell(subs, w);
+                subs = &rule;
+            }
+            if self.unalign {
+                //separator analyzers leave the padding in the cell, and the spacing of the previous separator too
+                if c >= first_real {
+                    subs = subs.trim_end();
+                }
+                if c > first_real && res.s.ends_with(char::is_whitespace) {
+                    subs = subs.trim_start();
+                }
+                w = expanded_width(subs, tw);
+            }
+            let truncated;
+            //the opening part of a cell going on on the next line is data that can't be cut
+            let tail = l.continues && c == last;
+            if self.overflow == Overflow::Truncate && c >= first_real && !tail && expanded_width(subs, tw) > w {
+                truncated = truncate_to_width(subs, w);
+                subs = &truncated;
+            }
+            let explicit_align = style.and_then(|st|st.align.as_ref()).or(fmt.column_align(c));
+            let mut align = explicit_align.unwrap_or(&self.align);
+            let col_fill = style.and_then(|st|st.fill).map(|f|f.to_string());
+            let col_fill_str = col_fill.as_deref().unwrap_or(&fill_str);
+
+            let numeric = if explicit_align.is_none() && !self.unalign { fmt.numeric_column(c) } else { None };
+            let mut left = 0;
+            if let Some((int_w, frac_w)) = numeric {
+                match self.numeric_align {
+                    NumericAlign::Off => {},
+                    NumericAlign::Right => align = &Align::Right,
+                    NumericAlign::Decimal => {
+                        //integer part is padded on the left, fraction part on the right
+                        let (int_part, _) = split_number(subs, fmt.decimal_sep).unwrap_or((display_width(subs), 0));
+                        w = w.max(int_w + frac_w);
+                        left = int_w.saturating_sub(int_part);
+                        align = &Align::Left;
+                    },
+                }
+            }
+
+            let natural = expanded_width(subs, tw) + left;
+            let mut delta = self.fill_count as usize;
+            if natural > w {
+                debt += natural - w;
+            }else{
+                let pay = debt.min(w - natural);
+                debt -= pay;
+                delta += w - natural - pay;
+            }
+            let lead = leading_fill(delta, align);
+            let wrapped = if c == last && c >= first_real && s.sep == '\0' && !tail { self.wrap_cell(subs, res.pos + left + lead, tw) } else { None };
+            res.pad(col_fill_str, left + lead);
+            if let Some(w) = wrapped {
+                res.push_str(&w);
+            }else{
+                res.push_str(subs);
+                if c != last || s.sep != '\0' || self.rectangular {
+                    res.pad(col_fill_str, delta - lead);
+                }
+            }
 
             if !explicit_join && s.sep != '\0' {
-                if let Some(sep_cfg) = self.find_sep_config(s.sep) {
-                    res.push_str(&sep_cfg.sep_str);
+                let sep = self.sep_string(s.sep, style);
+                if org_rule {
+                    res.push_str(&sep.replace(' ', "-"));
                 }else{
-                    res.push(s.sep);
+                    res.push_str(&sep);
+                }
+            }else if !explicit_join && c != last && !self.unalign {
+                //separator other lines have here, its place is kept for the following columns
+                if let Some(sep) = fmt.col_seps.get(c).filter(|sep|**sep != '\0') {
+                    res.pad(&fill_str, display_width(&self.sep_string(*sep, style)));
                 }
             }
         }
 
-        Some(res)
-    }
-
-    pub fn parse_args(&mut self, mut arg_it :std::slice::Iter<String>) -> Result<(), Box<dyn std::error::Error>> {
-        let mut sep_cfgs : Vec<SeparatorConfig> = vec![];
-        loop 
-        {
-            if let Some(arg) = arg_it.next() {
-               if arg == "--align" {
-                   if let Some(align_str) = arg_it.next() {
-                       if let Ok(al) = align_str.parse::<Align>() {
-                           self.align = al;
-                       }
-                   }
-               }else if arg == "--fill" {
-                   if let Some(fill_str) = arg_it.next() {
-                       self.fill = fill_str.chars().next().unwrap();
-                   }
-               }else if arg == "--fill_count" {
-                   if let Some(fill_count_str) = arg_it.next() {
-                       self.fill_count = fill_count_str.parse().unwrap_or(1);
-                   }
-               }else if arg == "--join" {
-                   if let Some(join_str) = arg_it.next() {
-                       self.join = join_str.clone();
-                   }
-               }else if arg == "--non_matched_as_is" {
-                   self.non_matched_as_is = true;
-               }else if arg == "--sep_config" {
-                   if let Some(cfg_str) = arg_it.next() {
-                       if let Ok(cfg) = cfg_str.parse::<SeparatorConfig>() {
-                           sep_cfgs.push(cfg);
-                       }
-                   }
-               }
-            }else {
-                break;
-            }
-        };
-        self.set_separator_configs(sep_cfgs);
-        Ok(())
+        Some(res.s)
     }
 }
\ No newline at end of file
@@ -0,0 +1,138 @@
+use crate::column_tools::ParseErr;
+use crate::column_tools::expanded_width;
+
+/// Comment syntax of a language: a line comment (`//`, `#`, `--`)
+/// or a block comment (`/* */`, `<!-- -->`) that is trailing only if it closes at the end of the line.
+/// Parsed from `OPEN` or `OPEN CLOSE`.
+#[derive(Clone)]
+pub struct CommentMarker
+{
+    open : String,
+    close : Option<String>,
+}
+
+impl CommentMarker {
+    pub fn line(open :&str) -> Self
+    {
+        Self{open : open.to_string(), close : None}
+    }
+
+    pub fn block(open :&str, close :&str) -> Self
+    {
+        Self{open : open.to_string(), close : Some(close.to_string())}
+    }
+
+    pub fn open(&self) -> &str
+    {
+        &self.open
+    }
+
+    //comment starting at the beginning of `rest`, None if the marker doesn't start it or doesn't end the line
+    fn comment_len(&self, rest :&str) -> Option<usize>
+    {
+        let body = rest.strip_prefix(self.open.as_str())?;
+        match &self.close {
+            None => Some(rest.len()),
+            Some(c) => if body.ends_with(c.as_str()) { Some(rest.len()) } else { None },
+        }
+    }
+}
+
+impl std::str::FromStr for CommentMarker {
+    type Err = ParseErr;
+
+    fn from_str(s :&str) -> Result<Self, Self::Err>
+    {
+        let mut it = s.split_whitespace();
+        match (it.next(), it.next(), it.next()) {
+            (Some(open), None, _) => Ok(CommentMarker::line(open)),
+            (Some(open), Some(close), None) => Ok(CommentMarker::block(open, close)),
+            _ => Err(ParseErr::new("comment marker or open and close markers", s)),
+        }
+    }
+}
+
+/// `//` and `/* */`
+pub fn default_markers() -> Vec<CommentMarker>
+{
+    vec![CommentMarker::line("//"), CommentMarker::block("/*", "*/")]
+}
+
+/// Where trailing comments are aligned to
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum CommentColumn {
+    Shared,       //one space after the longest line with a comment of the block
+    Fixed(usize), //given number of cells before the comment, one space after longer lines
+}
+
+/// Splits the line into the code (trimmed) and its trailing comment.
+/// Markers inside double-quoted strings and char literals don't count.
+/// Lines with nothing but a comment have no trailing comment.
+pub fn split_trailing_comment<'a>(l :&'a str, markers :&[CommentMarker]) -> (&'a str, Option<&'a str>)
+{
+    let b = l.as_bytes();
+    let mut in_str = false;
+    let mut i = 0;
+    while i < b.len() {
+        match b[i] {
+            b'\\' if in_str => i += 1,
+            b'"' => in_str = !in_str,
+            //char literals like '"', not lifetimes
+            b'\'' if !in_str => {
+                let lit = if b.get(i + 1) == Some(&b'\\') { 4 } else { l[i + 1..].chars().next().map_or(2, |c|c.len_utf8() + 2) };
+                if l.get(i + lit - 1..i + lit) == Some("'") {
+                    i += lit - 1;
+                }
+            },
+            _ if !in_str && l.is_char_boundary(i) => {
+                if let Some(len) = markers.iter().find_map(|m|m.comment_len(&l[i..])) {
+                    let code = l[..i].trim_end();
+                    if code.trim_start().is_empty() {
+                        return (l, None);
+                    }
+                    return (code, Some(&l[i..i + len]));
+                }
+            },
+            _ => {}
+        }
+        i += 1;
+    }
+    (l, None)
+}
+
+//width of the last line of a formatted line, wrapping may have split it
+fn last_line_width(l :&str, tab_width :usize) -> usize
+{
+    expanded_width(l.rsplit('\n').next().unwrap_or(l), tab_width)
+}
+
+/// Appends the comment to the formatted line, `at` cells from its start or after one space if the line is longer
+pub fn append_comment(l :&mut String, comment :&str, at :usize, tab_width :usize)
+{
+    let w = last_line_width(l, tab_width);
+    l.push_str(&" ".repeat(if w < at { at - w } else { 1 }));
+    l.push_str(comment);
+}
+
+/// Appends the comments to the formatted lines of a block, aligned to `column`
+pub fn append_comments(lines :&mut [String], comments :&[Option<&str>], column :&CommentColumn, tab_width :usize)
+{
+    let at = match column {
+        CommentColumn::Fixed(n) => *n,
+        CommentColumn::Shared => lines.iter().zip(comments.iter())
+            .filter_map(|(l, c)|c.map(|_|shared_column(l, tab_width))).max().unwrap_or(0),
+    };
+    for (l, c) in lines.iter_mut().zip(comments.iter()) {
+        if let Some(c) = c {
+            append_comment(l, c, at, tab_width);
+        }
+    }
+}
+
+/// Column right for a comment after the formatted line, `CommentColumn::Shared` is the maximum of the block
+pub fn shared_column(l :&str, tab_width :usize) -> usize
+{
+    last_line_width(l, tab_width) + 1
+}
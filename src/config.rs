@@ -0,0 +1,662 @@
+use std::error::Error;
+
+use crate::analyzers::LineAnalyzer;
+use crate::analyzers::assignment::TypeVarAnalyzer as AssignmentAnalyzer;
+use crate::analyzers::assignment::VarAnalyzer as AssignmentVarAnalyzer;
+use crate::analyzers::func_decl::Analyzer as FuncDeclAnalyzer;
+use crate::analyzers::xml_attr::Analyzer as XmlAttrAnalyzer;
+use crate::analyzers::var_decl::Analyzer as VarDeclAnalyzer;
+use crate::analyzers::bit_field::Analyzer as BitFieldAnalyzer;
+use crate::analyzers::cmnt_struct::Analyzer as CommentStructAnalyzer;
+use crate::analyzers::pipe_table::Analyzer as PipeTableAnalyzer;
+use crate::analyzers::csv::Analyzer as CsvAnalyzer;
+use crate::analyzers::rust_decl::Analyzer as RustDeclAnalyzer;
+use crate::analyzers::match_arm::Analyzer as MatchArmAnalyzer;
+use crate::analyzers::continuation::Analyzer as ContinuationAnalyzer;
+use crate::analyzers::continuation::ContinuationColumn;
+use crate::analyzers::separators::Analyzer as SepLineAnalyzer;
+use crate::analyzers::separators::Boundary;
+use crate::analyzers::separators::BoundType;
+
+use crate::comments::CommentColumn;
+use crate::comments::CommentMarker;
+use crate::comments::default_markers;
+
+use crate::auto_config::AutoMode;
+use crate::auto_config::AutoConfigResult;
+use crate::auto_config::auto_analyze_lines;
+use crate::auto_config::do_auto_config;
+
+use crate::column_tools::Align;
+use crate::column_tools::ColumnStyle;
+use crate::column_tools::Formatter;
+use crate::column_tools::IndentPolicy;
+use crate::column_tools::NumericAlign;
+use crate::column_tools::Overflow;
+use crate::column_tools::ParseErr;
+use crate::column_tools::TabMode;
+use crate::column_tools::Printer;
+use crate::column_tools::SeparatorConfig;
+
+use crate::segments::Segmentation;
+
+pub type DynLineAnalyzer = Box<dyn LineAnalyzer>;
+type ACreate = Box<dyn Fn(&Config)->DynLineAnalyzer>;
+type AnalyzerFactory = std::collections::HashMap<&'static str, ACreate>;
+
+fn analyzer_factory() -> AnalyzerFactory
+{
+    let mut factory : AnalyzerFactory = AnalyzerFactory::new();
+    factory.insert("sep"           , Box::new(|cfg|Box::new(cfg.make_sep_analyzer()   )));
+    factory.insert("bit_field"     , Box::new(|_  |Box::new(BitFieldAnalyzer{}         )));
+    factory.insert("comment_struct", Box::new(|_  |Box::new(CommentStructAnalyzer::new())));
+    factory.insert("var_decl"      , Box::new(|_  |Box::new(VarDeclAnalyzer{}          )));
+    factory.insert("xml"           , Box::new(|_  |Box::new(XmlAttrAnalyzer{}          )));
+    factory.insert("func_decl"     , Box::new(|_  |Box::new(FuncDeclAnalyzer{}         )));
+    factory.insert("assign_var"    , Box::new(|_  |Box::new(AssignmentVarAnalyzer{}    )));
+    factory.insert("assign_init"   , Box::new(|_  |Box::new(AssignmentAnalyzer{}       )));
+    factory.insert("pipe_table"    , Box::new(|_  |Box::new(PipeTableAnalyzer{}        )));
+    factory.insert("rust_decl"     , Box::new(|_  |Box::new(RustDeclAnalyzer{}         )));
+    factory.insert("match_arm"     , Box::new(|_  |Box::new(MatchArmAnalyzer{}         )));
+    factory.insert("continuation"  , Box::new(|_  |Box::new(ContinuationAnalyzer{}     )));
+    factory.insert("csv"           , Box::new(|cfg|Box::new(cfg.make_csv_analyzer(',') )));
+    factory.insert("tsv"           , Box::new(|cfg|Box::new(cfg.make_csv_analyzer('\t'))));
+    factory
+}
+
+/// Names accepted by `Config::analyzer` and `--analyzer`
+pub fn analyzer_names() -> Vec<&'static str>
+{
+    let mut names : Vec<&'static str> = analyzer_factory().keys().copied().collect();
+    names.sort_unstable();
+    names
+}
+
+//later entries replace earlier ones with the same key, so that the command line overrides the profile
+fn push_replacing<T, K :PartialEq>(v :&mut Vec<T>, item :T, key :impl Fn(&T) -> K)
+{
+    let k = key(&item);
+    v.retain(|x|key(x) != k);
+    v.push(item);
+}
+
+//invalid option values are reported with the option they belong to
+fn option_err(opt :&str, value :&str, err :ParseErr) -> Box<dyn Error>
+{
+    format!("{} {}: {}", opt, value, err).into()
+}
+
+/// Value following a value-taking command line option
+pub fn option_value<'a>(opt :&str, arg_it :&mut std::slice::Iter<'a, String>) -> Result<&'a String, Box<dyn Error>>
+{
+    arg_it.next().ok_or_else(||format!("{}: missing value", opt).into())
+}
+
+//single char option value, `tab` or `\t` for a tab
+fn char_arg(opt :&str, value :&str) -> Result<char, Box<dyn Error>>
+{
+    if value == "tab" || value == "\\t" {
+        return Ok('\t');
+    }
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(option_err(opt, value, ParseErr::new("single char or tab", value))),
+    }
+}
+
+#[derive(Clone)]
+pub enum AnalyzerChoice {
+    Auto,            //detect the mode from all the lines
+    Mode(AutoMode),  //preset of a given mode, same as in auto detection
+    Named(String),   //analyzer from the factory, configured by the Config
+}
+
+/// Typed configuration of a formatting run.
+/// Printer, formatter and separator analyzer settings are only used with `AnalyzerChoice::Named`,
+/// auto modes come with their own presets.
+/// Output settings (column styles, numeric alignment, tabs, indentation, width budgets, wrapping, rectangular mode, unaligning, continuation column, trailing comments) are applied in both cases.
+#[derive(Clone)]
+pub struct Config
+{
+    analyzer : AnalyzerChoice,
+    //separator analyzer
+    seps : Vec<char>,
+    seps_new_column : Vec<char>,
+    boundaries : Vec<(Boundary, BoundType)>,
+    //csv analyzer
+    csv_delimiter : Option<char>,
+    csv_quote : char,
+    csv_escape : Option<char>,
+    csv_header : bool,
+    //printer
+    align : Align,
+    fill : char,
+    fill_count : u8,
+    join : String,
+    non_matched_as_is : bool,
+    sep_configs : Vec<SeparatorConfig>,
+    column_styles : Vec<ColumnStyle>,
+    numeric_align : NumericAlign,
+    decimal_sep : char,
+    rectangular : bool,
+    tab_width : usize,
+    tab_mode : TabMode,
+    indent : IndentPolicy,
+    max_column_width : Option<usize>,
+    max_line_width : Option<usize>,
+    outlier_percent : Option<usize>,
+    overflow : Overflow,
+    wrap : Option<usize>,
+    unalign : bool,
+    //continuation backslashes
+    continuation_column : Option<ContinuationColumn>,
+    //trailing comments
+    comment_column : Option<CommentColumn>,
+    comment_markers : Option<Vec<CommentMarker>>,
+    //formatter
+    line_starts_to_ignore : Vec<String>,
+    add_pre_start : bool,
+    segmentation : Vec<Segmentation>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Config
+{
+    pub fn new() -> Self
+    {
+        Self{
+            analyzer : AnalyzerChoice::Named(String::from("sep")),
+            seps : vec![' '],
+            seps_new_column : Vec::new(),
+            boundaries : Vec::new(),
+            csv_delimiter : None,
+            csv_quote : '"',
+            csv_escape : None,
+            csv_header : false,
+            align : Align::Center,
+            fill : ' ',
+            fill_count : 0,
+            join : String::new(),
+            non_matched_as_is : false,
+            sep_configs : Vec::new(),
+            column_styles : Vec::new(),
+            numeric_align : NumericAlign::Off,
+            decimal_sep : '.',
+            rectangular : false,
+            tab_width : 0,
+            tab_mode : TabMode::Spaces,
+            indent : IndentPolicy::Max,
+            max_column_width : None,
+            max_line_width : None,
+            outlier_percent : None,
+            overflow : Overflow::Realign,
+            wrap : None,
+            unalign : false,
+            continuation_column : None,
+            comment_column : None,
+            comment_markers : None,
+            line_starts_to_ignore : Vec::new(),
+            add_pre_start : false,
+            segmentation : Vec::new(),
+        }
+    }
+
+    pub fn analyzer(mut self, name :&str) -> Self
+    {
+        self.analyzer = AnalyzerChoice::Named(name.to_string());
+        self
+    }
+
+    pub fn auto(mut self) -> Self
+    {
+        self.analyzer = AnalyzerChoice::Auto;
+        self
+    }
+
+    pub fn auto_mode(mut self, m :AutoMode) -> Self
+    {
+        self.analyzer = AnalyzerChoice::Mode(m);
+        self
+    }
+
+    pub fn separators(mut self, seps :Vec<char>) -> Self
+    {
+        self.seps = seps;
+        self
+    }
+
+    pub fn new_column_separators(mut self, seps :Vec<char>) -> Self
+    {
+        self.seps_new_column = seps;
+        self
+    }
+
+    pub fn boundary(mut self, bnd :Boundary, bt :BoundType) -> Self
+    {
+        push_replacing(&mut self.boundaries, (bnd, bt), |(b, _)|b.chars());
+        self
+    }
+
+    /// Delimiter of the `csv` and `tsv` analyzers, instead of `,` and tab
+    pub fn csv_delimiter(mut self, c :char) -> Self
+    {
+        self.csv_delimiter = Some(c);
+        self
+    }
+
+    pub fn csv_quote(mut self, c :char) -> Self
+    {
+        self.csv_quote = c;
+        self
+    }
+
+    /// Char escaping the quote inside quoted fields, the quote itself (doubling) by default
+    pub fn csv_escape(mut self, c :char) -> Self
+    {
+        self.csv_escape = Some(c);
+        self
+    }
+
+    /// The first record is a header, see `csv::Analyzer::set_header`
+    pub fn csv_header(mut self, val :bool) -> Self
+    {
+        self.csv_header = val;
+        self
+    }
+
+    pub fn align(mut self, a :Align) -> Self
+    {
+        self.align = a;
+        self
+    }
+
+    pub fn fill(mut self, c :char) -> Self
+    {
+        self.fill = c;
+        self
+    }
+
+    pub fn fill_count(mut self, cnt :u8) -> Self
+    {
+        self.fill_count = cnt;
+        self
+    }
+
+    pub fn join(mut self, j :&str) -> Self
+    {
+        self.join = j.to_string();
+        self
+    }
+
+    pub fn non_matched_as_is(mut self, val :bool) -> Self
+    {
+        self.non_matched_as_is = val;
+        self
+    }
+
+    pub fn separator_config(mut self, cfg :SeparatorConfig) -> Self
+    {
+        push_replacing(&mut self.sep_configs, cfg, |c|c.sep());
+        self
+    }
+
+    /// Column styles are applied on top of the auto mode presets as well
+    pub fn column_style(mut self, st :ColumnStyle) -> Self
+    {
+        self.column_styles.push(st);
+        self
+    }
+
+    /// Numeric alignment is applied on top of the auto mode presets as well
+    pub fn numeric_align(mut self, na :NumericAlign) -> Self
+    {
+        self.numeric_align = na;
+        self
+    }
+
+    pub fn decimal_separator(mut self, sep :char) -> Self
+    {
+        self.decimal_sep = sep;
+        self
+    }
+
+    /// Pads the last column of each line as well, so the block has no ragged right edge
+    pub fn rectangular(mut self, val :bool) -> Self
+    {
+        self.rectangular = val;
+        self
+    }
+
+    /// Tabs are expanded to multiples of `tab_width` when measuring, 0 counts a tab as 1 cell
+    pub fn tab_width(mut self, tab_width :usize) -> Self
+    {
+        self.tab_width = tab_width;
+        self
+    }
+
+    pub fn tab_mode(mut self, tm :TabMode) -> Self
+    {
+        self.tab_mode = tm;
+        self
+    }
+
+    /// Indentation of the aligned lines when the pre-start column is used (`prestart` and all auto modes)
+    pub fn indent(mut self, policy :IndentPolicy) -> Self
+    {
+        self.indent = policy;
+        self
+    }
+
+    /// Cells wider than `max` don't widen their column
+    pub fn max_column_width(mut self, max :usize) -> Self
+    {
+        self.max_column_width = Some(max);
+        self
+    }
+
+    /// Columns are narrowed so that aligned lines fit in `max` cells
+    pub fn max_line_width(mut self, max :usize) -> Self
+    {
+        self.max_line_width = Some(max);
+        self
+    }
+
+    /// The widest `percent` % of the cells of each column don't widen it
+    pub fn outlier_percent(mut self, percent :usize) -> Self
+    {
+        self.outlier_percent = Some(percent);
+        self
+    }
+
+    /// What happens to the cells over the budgets
+    pub fn overflow(mut self, o :Overflow) -> Self
+    {
+        self.overflow = o;
+        self
+    }
+
+    /// Word-wraps the last column of lines longer than `width`
+    pub fn wrap(mut self, width :usize) -> Self
+    {
+        self.wrap = Some(width);
+        self
+    }
+
+    /// Collapses the alignment padding instead of adding it, so that formatting and unaligning round-trip
+    pub fn unalign(mut self, val :bool) -> Self
+    {
+        self.unalign = val;
+        self
+    }
+
+    /// Column of the line continuation backslashes, see the continuation analyzer
+    pub fn continuation_column(mut self, column :ContinuationColumn) -> Self
+    {
+        self.continuation_column = Some(column);
+        self
+    }
+
+    /// Cuts trailing comments off before the analysis and aligns them to `column` after the code is formatted
+    pub fn align_comments(mut self, column :CommentColumn) -> Self
+    {
+        self.comment_column = Some(column);
+        self
+    }
+
+    /// Comment syntax of the input for `align_comments`, `//` and `/* */` by default
+    pub fn comment_marker(mut self, marker :CommentMarker) -> Self
+    {
+        push_replacing(self.comment_markers.get_or_insert_with(Vec::new), marker, |m|m.open().to_string());
+        self
+    }
+
+    /// Column and markers of the trailing comments, None if they are not aligned
+    pub fn trailing_comments(&self) -> Option<(&CommentColumn, Vec<CommentMarker>)>
+    {
+        let markers = self.comment_markers.clone().unwrap_or_else(default_markers);
+        self.comment_column.as_ref().map(|c|(c, markers))
+    }
+
+    pub fn line_start_to_ignore(mut self, s :&str) -> Self
+    {
+        push_replacing(&mut self.line_starts_to_ignore, s.to_string(), |l|l.clone());
+        self
+    }
+
+    pub fn prestart(mut self, val :bool) -> Self
+    {
+        self.add_pre_start = val;
+        self
+    }
+
+    /// Splits the input into blocks aligned independently, several strategies can be combined.
+    /// A strategy of the same kind (e.g. another regex) replaces the earlier one
+    pub fn segmentation(mut self, seg :Segmentation) -> Self
+    {
+        push_replacing(&mut self.segmentation, seg, std::mem::discriminant);
+        self
+    }
+
+    pub fn segmentations(&self) -> &[Segmentation]
+    {
+        &self.segmentation
+    }
+
+    pub fn analyzer_choice(&self) -> &AnalyzerChoice
+    {
+        &self.analyzer
+    }
+
+    fn make_sep_analyzer(&self) -> SepLineAnalyzer
+    {
+        let mut sa = SepLineAnalyzer::new();
+        sa.set_separators(self.seps.clone());
+        sa.set_new_column_separators(self.seps_new_column.clone());
+        for (bnd, bt) in self.boundaries.iter() {
+            sa.add_boundary(bnd.clone(), bt.clone());
+        }
+        sa
+    }
+
+    fn make_csv_analyzer(&self, default_delimiter :char) -> CsvAnalyzer
+    {
+        let mut ca = CsvAnalyzer::new(self.csv_delimiter.unwrap_or(default_delimiter));
+        ca.set_quote(self.csv_quote);
+        if let Some(esc) = self.csv_escape {
+            ca.set_escape(esc);
+        }
+        ca.set_header(self.csv_header);
+        ca
+    }
+
+    fn make_printer(&self) -> Printer
+    {
+        let mut printer = Printer::new(self.align.clone(), self.fill, self.fill_count, self.join.clone(), self.non_matched_as_is);
+        printer.set_separator_configs(self.sep_configs.clone());
+        printer
+    }
+
+    //settings that are applied to both named analyzers and auto presets
+    fn apply_common(&self, res :&mut AutoConfigResult)
+    {
+        //explicit column styles go after it, so they win
+        if let Some(c) = &self.continuation_column {
+            res.printer.add_column_styles(vec![c.column_style()]);
+        }
+        res.printer.add_column_styles(self.column_styles.clone());
+        res.printer.set_numeric_align(self.numeric_align.clone());
+        res.formatter.set_decimal_separator(self.decimal_sep);
+        res.printer.set_rectangular(self.rectangular);
+        res.printer.set_tab_mode(self.tab_mode.clone());
+        res.formatter.set_tab_width(self.tab_width);
+        res.formatter.set_indent_policy(self.indent.clone());
+        res.formatter.set_max_column_width(self.max_column_width, self.overflow == Overflow::Truncate);
+        res.formatter.set_outlier_percent(self.outlier_percent);
+        res.printer.set_max_line_width(self.max_line_width);
+        res.printer.set_overflow(self.overflow.clone());
+        res.printer.set_wrap(self.wrap);
+        res.printer.set_unalign(self.unalign);
+    }
+
+    fn make_formatter(&self) -> Formatter
+    {
+        let mut fmtr = Formatter::new();
+        fmtr.set_line_starts_to_ignore(self.line_starts_to_ignore.clone());
+        fmtr.set_add_pre_start(self.add_pre_start);
+        fmtr
+    }
+
+    /// Creates the analyzer, formatter and printer for a run.
+    /// `lines` are only used for the auto detection.
+    pub fn build(&self, lines :&[&str]) -> Result<AutoConfigResult, Box<dyn Error>>
+    {
+        let mut res = match &self.analyzer {
+            AnalyzerChoice::Auto => do_auto_config(auto_analyze_lines(lines)),
+            AnalyzerChoice::Mode(m) => do_auto_config(m.clone()),
+            AnalyzerChoice::Named(name) => {
+                let factory = analyzer_factory();
+                let create = factory.get(name.as_str()).ok_or_else(||format!("unknown analyzer: {}", name))?;
+                AutoConfigResult{analyzer : create(self), formatter : self.make_formatter(), printer : self.make_printer()}
+            }
+        };
+        self.apply_common(&mut res);
+        Ok(res)
+    }
+
+    pub fn parse_args(&mut self, arg_it :std::slice::Iter<String>) -> Result<(), Box<dyn Error>> {
+        self.parse_options(arg_it, false)
+    }
+
+    /// Same as `parse_args`, but every argument has to be an option of the config, like the ones from a profile
+    pub fn parse_args_strict(&mut self, arg_it :std::slice::Iter<String>) -> Result<(), Box<dyn Error>> {
+        self.parse_options(arg_it, true)
+    }
+
+    //unknown arguments are skipped unless `strict`, the command line holds the options of main as well
+    fn parse_options(&mut self, mut arg_it :std::slice::Iter<String>, strict :bool) -> Result<(), Box<dyn Error>> {
+        let factory = analyzer_factory();
+        while let Some(arg) = arg_it.next() {
+           if arg == "--analyzer" {
+               let atype = option_value(arg, &mut arg_it)?;
+               if !factory.contains_key(atype.as_str()) {
+                   return Err(option_err(arg, atype, ParseErr::new(&analyzer_names().join(", "), atype)));
+               }
+               self.analyzer = AnalyzerChoice::Named(atype.clone());
+           }else if arg == "--auto" {
+               self.analyzer = AnalyzerChoice::Auto;
+           }else if arg == "--include" || arg == "--exclude" {
+               let bound_str = option_value(arg, &mut arg_it)?;
+               let bnd = bound_str.parse::<Boundary>().map_err(|e|option_err(arg, bound_str, e))?;
+               let bt = arg[2..].parse::<BoundType>()?;
+               push_replacing(&mut self.boundaries, (bnd, bt), |(b, _)|b.chars());
+           }else if arg == "--seps" {
+               let seps = option_value(arg, &mut arg_it)?;
+               self.seps = seps.chars().collect();
+           }else if arg == "--new_column_seps" {
+               let seps = option_value(arg, &mut arg_it)?;
+               self.seps_new_column = seps.chars().collect();
+           }else if arg == "--csv_delimiter" || arg == "--csv-delimiter" {
+               let c = option_value(arg, &mut arg_it)?;
+               self.csv_delimiter = Some(char_arg(arg, c)?);
+           }else if arg == "--csv_quote" || arg == "--csv-quote" {
+               let c = option_value(arg, &mut arg_it)?;
+               self.csv_quote = char_arg(arg, c)?;
+           }else if arg == "--csv_escape" || arg == "--csv-escape" {
+               let c = option_value(arg, &mut arg_it)?;
+               self.csv_escape = Some(char_arg(arg, c)?);
+           }else if arg == "--csv_header" || arg == "--csv-header" {
+               self.csv_header = true;
+           }else if arg == "--align" {
+               let align_str = option_value(arg, &mut arg_it)?;
+               self.align = align_str.parse::<Align>().map_err(|e|option_err(arg, align_str, e))?;
+           }else if arg == "--fill" {
+               let fill_str = option_value(arg, &mut arg_it)?;
+               self.fill = char_arg(arg, fill_str)?;
+           }else if arg == "--fill_count" {
+               let fill_count_str = option_value(arg, &mut arg_it)?;
+               self.fill_count = fill_count_str.parse().map_err(|e|format!("{} {}: {}", arg, fill_count_str, e))?;
+           }else if arg == "--join" {
+               let join_str = option_value(arg, &mut arg_it)?;
+               self.join = join_str.clone();
+           }else if arg == "--non_matched_as_is" {
+               self.non_matched_as_is = true;
+           }else if arg == "--sep_config" {
+               let cfg_str = option_value(arg, &mut arg_it)?;
+               let cfg = cfg_str.parse::<SeparatorConfig>().map_err(|e|option_err(arg, cfg_str, e))?;
+               push_replacing(&mut self.sep_configs, cfg, |c|c.sep());
+           }else if arg == "--column" {
+               let style_str = option_value(arg, &mut arg_it)?;
+               let st = style_str.parse::<ColumnStyle>().map_err(|e|option_err(arg, style_str, e))?;
+               self.column_styles.push(st);
+           }else if arg == "--numeric" {
+               let na_str = option_value(arg, &mut arg_it)?;
+               self.numeric_align = na_str.parse::<NumericAlign>().map_err(|e|option_err(arg, na_str, e))?;
+           }else if arg == "--decimal_sep" {
+               let sep_str = option_value(arg, &mut arg_it)?;
+               self.decimal_sep = char_arg(arg, sep_str)?;
+           }else if arg == "--rectangular" {
+               self.rectangular = true;
+           }else if arg == "--tab_width" || arg == "--tab-width" {
+               let tw_str = option_value(arg, &mut arg_it)?;
+               self.tab_width = tw_str.parse::<usize>().map_err(|e|format!("{} {}: {}", arg, tw_str, e))?;
+           }else if arg == "--tabs" {
+               let tm_str = option_value(arg, &mut arg_it)?;
+               self.tab_mode = tm_str.parse::<TabMode>().map_err(|e|option_err(arg, tm_str, e))?;
+           }else if arg == "--indent" {
+               let ind_str = option_value(arg, &mut arg_it)?;
+               self.indent = ind_str.parse::<IndentPolicy>().map_err(|e|option_err(arg, ind_str, e))?;
+           }else if arg == "--max_column_width" || arg == "--max-column-width" {
+               let w_str = option_value(arg, &mut arg_it)?;
+               let w = w_str.parse::<usize>().map_err(|e|format!("{} {}: {}", arg, w_str, e))?;
+               if w == 0 {
+                   return Err(format!("{} {}: width must be at least 1", arg, w_str).into());
+               }
+               self.max_column_width = Some(w);
+           }else if arg == "--max_line_width" || arg == "--max-line-width" {
+               let w_str = option_value(arg, &mut arg_it)?;
+               self.max_line_width = Some(w_str.parse::<usize>().map_err(|e|format!("{} {}: {}", arg, w_str, e))?);
+           }else if arg == "--outlier_percent" || arg == "--outlier-percent" {
+               let p_str = option_value(arg, &mut arg_it)?;
+               self.outlier_percent = Some(p_str.parse::<usize>().map_err(|e|format!("{} {}: {}", arg, p_str, e))?);
+           }else if arg == "--overflow" {
+               let o_str = option_value(arg, &mut arg_it)?;
+               self.overflow = o_str.parse::<Overflow>().map_err(|e|option_err(arg, o_str, e))?;
+           }else if arg == "--wrap" {
+               let w_str = option_value(arg, &mut arg_it)?;
+               self.wrap = Some(w_str.parse::<usize>().map_err(|e|format!("{} {}: {}", arg, w_str, e))?);
+           }else if arg == "--unalign" {
+               self.unalign = true;
+           }else if arg == "--continuation_column" || arg == "--continuation-column" {
+               let c_str = option_value(arg, &mut arg_it)?;
+               self.continuation_column = Some(c_str.parse::<ContinuationColumn>().map_err(|e|option_err(arg, c_str, e))?);
+           }else if arg == "--align_comments" || arg == "--align-comments" {
+               self.comment_column = Some(CommentColumn::Shared);
+           }else if arg == "--comment_column" || arg == "--comment-column" {
+               let c_str = option_value(arg, &mut arg_it)?;
+               self.comment_column = Some(CommentColumn::Fixed(c_str.parse::<usize>().map_err(|e|format!("{} {}: {}", arg, c_str, e))?));
+           }else if arg == "--comment_marker" || arg == "--comment-marker" {
+               let m_str = option_value(arg, &mut arg_it)?;
+               let marker = m_str.parse::<CommentMarker>().map_err(|e|option_err(arg, m_str, e))?;
+               push_replacing(self.comment_markers.get_or_insert_with(Vec::new), marker, |m|m.open().to_string());
+           }else if arg == "--line_start_to_ignore" {
+               let ignore = option_value(arg, &mut arg_it)?;
+               push_replacing(&mut self.line_starts_to_ignore, ignore.clone(), |l|l.clone());
+           }else if arg == "--segment" {
+               let seg_str = option_value(arg, &mut arg_it)?;
+               let seg = seg_str.parse::<Segmentation>().map_err(|e|option_err(arg, seg_str, e))?;
+               push_replacing(&mut self.segmentation, seg, std::mem::discriminant);
+           }else if arg == "--prestart" {
+               self.add_pre_start = true;
+           }else if strict {
+               return Err(format!("unknown option {}", arg).into());
+           }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+use std::error::Error;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::auto_config::cursor_block;
+use crate::config::Config;
+use crate::format_text_with_diagnostics;
+use crate::LineDiagnostic;
+use crate::lines::LineRange;
+
+//line without its terminator and the terminator ("\n", "\r\n" or "" for the last line)
+fn split_terminator(l :&str) -> (&str, &str)
+{
+    let content = l.strip_suffix('\n').map(|c|c.strip_suffix('\r').unwrap_or(c)).unwrap_or(l);
+    (content, &l[content.len()..])
+}
+
+/// Formats only the lines in `ranges`, every range is aligned on its own.
+/// Everything outside the ranges, including line terminators, stays byte-identical.
+/// Ranges reaching past the end of the input are cut, overlapping ranges are merged.
+/// Diagnostics refer to the lines of the whole input.
+pub fn format_line_ranges(input :&str, ranges :&[LineRange], cfg :&Config) -> Result<(String, Vec<LineDiagnostic>), Box<dyn Error>>
+{
+    let lines : Vec<&str> = input.split_inclusive('\n').collect();
+
+    let mut sorted = ranges.to_vec();
+    sorted.sort_by_key(|r|r.begin);
+    let mut merged : Vec<LineRange> = Vec::new();
+    for r in sorted {
+        if r.begin > lines.len() {
+            return Err(format!("line range {}:{} starts past the end of the input ({} lines)", r.begin, r.end, lines.len()).into());
+        }
+        match merged.last_mut() {
+            Some(m) if r.begin <= m.end + 1 => m.end = m.end.max(r.end),
+            _ => merged.push(r),
+        }
+    }
+
+    let mut res = String::with_capacity(input.len());
+    let mut diags : Vec<LineDiagnostic> = Vec::new();
+    let mut next = 0;
+    for r in merged {
+        let begin = r.begin - 1;
+        let end = r.end.min(lines.len());
+        lines[next..begin].iter().for_each(|l|res.push_str(l));
+
+        //trailing blank lines would be lost by the formatter, they are kept as they are
+        let text_end = begin + lines[begin..end].iter().rposition(|l|!l.trim().is_empty()).map_or(0, |i|i + 1);
+        if text_end > begin {
+            let (content, terms) : (Vec<&str>, Vec<&str>) = lines[begin..text_end].iter().map(|l|split_terminator(l)).unzip();
+            let (formatted, range_diags) = format_text_with_diagnostics(&content.join("\n"), cfg)?;
+            let out_lines : Vec<&str> = formatted.split('\n').collect();
+            if out_lines.len() == terms.len() {
+                for (l, t) in out_lines.iter().zip(terms.iter()) {
+                    res.push_str(l);
+                    res.push_str(t);
+                }
+            }else if out_lines.len() > terms.len() {
+                //wrapping added lines, they get the terminator of the first line
+                let term = if terms[0].is_empty() { "\n" } else { terms[0] };
+                res.push_str(&out_lines.join(term));
+                res.push_str(terms[terms.len() - 1]);
+            }else{
+                return Err(format!("line range {}:{}: formatting lost lines", r.begin, r.end).into());
+            }
+            diags.extend(range_diags.into_iter().map(|d|LineDiagnostic{line : d.line + begin, issue : d.issue}));
+        }
+        lines[text_end..end].iter().for_each(|l|res.push_str(l));
+        next = end;
+    }
+    lines[next..].iter().for_each(|l|res.push_str(l));
+    Ok((res, diags))
+}
+
+/// Formats only the block around the 1-based `line`, see `cursor_block`.
+/// The block is aligned in the mode detected for the cursor line, output settings of `cfg` still apply.
+/// Returns the formatted input and the block, so that the caller can show it.
+pub fn format_cursor_block(input :&str, line :usize, cfg :&Config) -> Result<(String, LineRange), Box<dyn Error>>
+{
+    let lines : Vec<&str> = input.lines().collect();
+    let (mode, range) = cursor_block(&lines, line).ok_or_else(||format!("line {} has nothing to align", line))?;
+    let (res, _) = format_line_ranges(input, &[range], &cfg.clone().auto_mode(mode))?;
+    Ok((res, range))
+}
+
+//creates a new temporary file next to `path`, never opening an existing file or following a symlink
+fn create_tmp(path :&Path, file_name :&std::ffi::OsStr) -> Result<(PathBuf, std::fs::File), std::io::Error>
+{
+    let seed = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map_or(0, |d|d.subsec_nanos());
+    let mut attempt = 0u32;
+    loop {
+        let mut tmp_name = std::ffi::OsString::from(".");
+        tmp_name.push(file_name);
+        tmp_name.push(format!(".{}.{:08x}.tmp", std::process::id(), seed.wrapping_add(attempt.wrapping_mul(0x9e37_79b9))));
+        let tmp_path = path.with_file_name(tmp_name);
+        match std::fs::OpenOptions::new().write(true).create_new(true).open(&tmp_path) {
+            Ok(f) => return Ok((tmp_path, f)),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists && attempt < 100 => attempt += 1,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Replaces the content of `path` atomically: writes a temporary file next to it and renames it over the original.
+/// A symlinked `path` is resolved first, so the link stays and its target gets the new content.
+/// With `backup_suffix` the original is kept as `path` + suffix.
+pub fn write_atomic(path :&Path, content :&str, backup_suffix :Option<&str>) -> Result<(), Box<dyn Error>>
+{
+    let err = |e :std::io::Error, p :&Path|format!("{}: {}", p.display(), e);
+    let given = path;
+    let target = std::fs::canonicalize(given).unwrap_or_else(|_|given.to_path_buf());
+    let path = target.as_path();
+    let file_name = path.file_name().ok_or_else(||format!("{}: not a file", path.display()))?;
+    let (tmp_path, mut f) = create_tmp(path, file_name).map_err(|e|err(e, path))?;
+
+    let mut write_tmp = || -> Result<(), std::io::Error> {
+        f.write_all(content.as_bytes())?;
+        f.sync_all()?;
+        if let Ok(meta) = std::fs::metadata(path) {
+            std::fs::set_permissions(&tmp_path, meta.permissions())?;
+        }
+        Ok(())
+    };
+    if let Err(e) = write_tmp() {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(err(e, &tmp_path).into());
+    }
+
+    if let Some(suffix) = backup_suffix {
+        let mut backup = given.as_os_str().to_owned();
+        backup.push(suffix);
+        let backup = PathBuf::from(backup);
+        if let Err(e) = std::fs::copy(path, &backup) {
+            let _ = std::fs::remove_file(&tmp_path);
+            return Err(err(e, &backup).into());
+        }
+    }
+
+    std::fs::rename(&tmp_path, path).map_err(|e|{
+        let _ = std::fs::remove_file(&tmp_path);
+        err(e, path)
+    })?;
+    Ok(())
+}
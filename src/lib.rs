@@ -0,0 +1,136 @@
+#[macro_use] 
+extern crate lazy_static;
+
+pub mod column_tools;
+pub mod analyzers;
+pub mod auto_config;
+pub mod config;
+pub mod lines;
+pub mod segments;
+pub mod comments;
+pub mod profiles;
+pub mod check;
+pub mod in_place;
+pub mod lsp;
+pub mod streaming;
+mod tests;
+
+use std::error::Error;
+
+use analyzers::AnalyzeErr;
+use column_tools::LineDescr;
+use segments::split_segments;
+use comments::append_comments;
+use comments::split_trailing_comment;
+
+pub use config::Config;
+pub use config::AnalyzerChoice;
+
+/// Why a line was written as is
+#[derive(Debug)]
+pub enum LineIssue {
+    Rejected(AnalyzeErr),                          //the analyzer didn't accept the line
+    ColumnCount{found : usize, expected : usize}, //kept as is by `non_matched_as_is`
+}
+
+impl std::fmt::Display for LineIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LineIssue::Rejected(e) => write!(f, "rejected: {}", e),
+            LineIssue::ColumnCount{found, expected} => write!(f, "{} columns instead of {}", found, expected),
+        }
+    }
+}
+
+/// Issue of the input line `line` (1-based)
+#[derive(Debug)]
+pub struct LineDiagnostic {
+    pub line : usize,
+    pub issue : LineIssue,
+}
+
+impl LineDiagnostic {
+    /// Byte offset in the line the issue points to, if known
+    pub fn offset(&self) -> Option<usize>
+    {
+        match &self.issue {
+            LineIssue::Rejected(e) => e.offset,
+            LineIssue::ColumnCount{..} => None,
+        }
+    }
+}
+
+/// Formats `input` according to `cfg` and returns the aligned text.
+/// Lines are joined with `\n`, trailing whitespace of the input lines is dropped.
+pub fn format_text(input :&str, cfg :&Config) -> Result<String, Box<dyn Error>>
+{
+    format_text_with_diagnostics(input, cfg).map(|(res, _)|res)
+}
+
+/// Same as `format_text`, additionally reports the lines that were not aligned and why.
+/// Blank lines, ignored lines (`line_start_to_ignore`) and lines kept as is by the segmentation are not reported.
+pub fn format_text_with_diagnostics(input :&str, cfg :&Config) -> Result<(String, Vec<LineDiagnostic>), Box<dyn Error>>
+{
+    let raw_lines : Vec<&str> = input.lines().collect();
+    let lines_str : Vec<&str> = raw_lines.iter().map(|l|l.trim_end()).collect();
+    //with aligned trailing comments the analysis only sees the code
+    let comments = cfg.trailing_comments();
+    let (code_str, line_comments) : (Vec<&str>, Vec<Option<&str>>) = match &comments {
+        Some((_, markers)) => lines_str.iter().map(|l|split_trailing_comment(l, markers)).unzip(),
+        None => lines_str.iter().map(|l|(*l, None)).unzip(),
+    };
+    let setup = cfg.build(&code_str)?;
+    let mut line_analyzer = setup.analyzer;
+    let mut fmtr = setup.formatter;
+    let mut printer = setup.printer;
+
+    let segments = split_segments(&code_str, cfg.segmentations(), line_analyzer.as_ref(), &fmtr);
+
+    let mut res : Vec<String> = Vec::with_capacity(lines_str.len());
+    let mut diags : Vec<LineDiagnostic> = Vec::new();
+    for seg in segments.iter() {
+        if !seg.format {
+            res.extend(lines_str[seg.begin..seg.end].iter().map(|l|l.to_string()));
+            continue;
+        }
+        let seg_lines = &code_str[seg.begin..seg.end];
+
+        let mut lines: Vec<LineDescr> = Vec::with_capacity(seg_lines.len());
+        seg_lines.iter().enumerate().for_each(|(i, l)|{
+           let mut line = LineDescr::new(l);
+           if let Err(e) = fmtr.analyze_line(line_analyzer.as_mut(), &mut line) {
+               if !l.is_empty() && !fmtr.check_line_start_to_ignore(l.trim_start()) {
+                   diags.push(LineDiagnostic{line : seg.begin + i + 1, issue : LineIssue::Rejected(e)});
+               }
+           }
+           lines.push(line); 
+        });
+
+        fmtr.finish();
+        if printer.non_matched_as_is() {
+            let expected = fmtr.column_count();
+            diags.extend(lines.iter().enumerate()
+                .filter(|(_, l)|l.column_count() > 0 && l.column_count() != expected)
+                .map(|(i, l)|LineDiagnostic{line : seg.begin + i + 1, issue : LineIssue::ColumnCount{found : l.column_count(), expected}}));
+            diags.sort_by_key(|d|d.line);
+        }
+        printer.set_formatter(fmtr);
+        //lines going on inside a cell keep their trailing whitespace
+        let mut seg_res : Vec<String> = lines.iter().enumerate().filter_map(|(i, l)|printer.format_line(l).map(|mut s|{
+            if l.continues() {
+                let raw = raw_lines[seg.begin + i];
+                s.push_str(&raw[raw.trim_end().len()..]);
+            }
+            s
+        })).collect();
+
+        fmtr = printer.take_formatter().unwrap();
+        if let Some((column, _)) = &comments {
+            append_comments(&mut seg_res, &line_comments[seg.begin..seg.end], column, fmtr.tab_width());
+        }
+        res.extend(seg_res);
+        fmtr.reset_columns();
+    }
+
+    Ok((res.join("\n"), diags))
+}
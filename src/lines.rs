@@ -0,0 +1,28 @@
+use crate::column_tools::ParseErr;
+
+/// Lines `begin..=end` of the input, 1-based, parsed from `START:END`
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+pub struct LineRange {
+    pub begin : usize,
+    pub end : usize,
+}
+
+impl std::str::FromStr for LineRange {
+    type Err = ParseErr;
+
+    fn from_str(s :&str) -> Result<Self, Self::Err>
+    {
+        let (b, e) = s.split_once(':').ok_or_else(||ParseErr::new("START:END", s))?;
+        let begin = b.trim().parse::<usize>().map_err(|e|ParseErr::from(e).context("start line", b, 0))?;
+        let end = e.trim().parse::<usize>().map_err(|err|ParseErr::from(err).context("end line", e, b.len() + 1))?;
+        if begin == 0 {
+            return Err(ParseErr::new("1-based start line", b).at(0));
+        }
+        if end < begin {
+            return Err(ParseErr::new("end line not before the start line", e).at(b.len() + 1));
+        }
+        Ok(LineRange{begin, end})
+    }
+}
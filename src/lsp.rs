@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::BufRead;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde_json::json;
+use serde_json::Value;
+
+use crate::config::Config;
+use crate::lines::LineRange;
+use crate::in_place::format_line_ranges;
+
+const METHOD_NOT_FOUND : i64 = -32601;
+const INVALID_PARAMS : i64 = -32602;
+const INTERNAL_ERROR : i64 = -32603;
+
+/// Reads one JSON-RPC message framed by a `Content-Length` header, `None` at the end of the input
+pub fn read_message<R :BufRead>(input :&mut R) -> Result<Option<Value>, Box<dyn Error>>
+{
+    let mut len : Option<usize> = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, val)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                len = Some(val.trim().parse::<usize>()?);
+            }
+        }
+    }
+
+    let mut body = vec![0u8; len.ok_or("message without Content-Length")?];
+    input.read_exact(&mut body)?;
+    Ok(Some(serde_json::from_slice(&body)?))
+}
+
+pub fn write_message<W :Write>(output :&mut W, msg :&Value) -> Result<(), Box<dyn Error>>
+{
+    let body = msg.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    output.flush()?;
+    Ok(())
+}
+
+//line content without the terminator
+fn line_content(l :&str) -> &str
+{
+    let l = l.strip_suffix('\n').unwrap_or(l);
+    l.strip_suffix('\r').unwrap_or(l)
+}
+
+fn utf16_len(s :&str) -> usize
+{
+    s.encode_utf16().count()
+}
+
+/// One `TextEdit` per changed line, replacing the line content and keeping its terminator.
+/// Old and new lines are compared pairwise, if wrapping changed the number of lines
+/// a single edit replaces the lines between the unchanged start and end of the text.
+pub fn text_edits(old :&str, new :&str) -> Vec<Value>
+{
+    let old_lines : Vec<&str> = old.split_inclusive('\n').collect();
+    let new_lines : Vec<&str> = new.split_inclusive('\n').collect();
+    if old_lines.len() != new_lines.len() {
+        let prefix = old_lines.iter().zip(new_lines.iter()).take_while(|(o, n)|o == n).count();
+        let max_suffix = old_lines.len().min(new_lines.len()) - prefix;
+        let suffix = old_lines.iter().rev().zip(new_lines.iter().rev()).take(max_suffix).take_while(|(o, n)|o == n).count();
+        let old_end = old_lines.len() - suffix;
+        let end = match old_lines.last() {
+            Some(last) if old_end == old_lines.len() && !last.ends_with('\n') => json!({"line" : old_end - 1, "character" : utf16_len(last)}),
+            _ => json!({"line" : old_end, "character" : 0}),
+        };
+        return vec![json!({
+            "range" : {"start" : {"line" : prefix, "character" : 0}, "end" : end},
+            "newText" : new_lines[prefix..new_lines.len() - suffix].concat(),
+        })];
+    }
+    old.split_inclusive('\n').zip(new.split_inclusive('\n')).enumerate()
+        .map(|(i, (o, n))|(i, line_content(o), line_content(n)))
+        .filter(|(_, o, n)|o != n)
+        .map(|(i, o, n)|json!({
+            "range" : {
+                "start" : {"line" : i, "character" : 0},
+                "end" : {"line" : i, "character" : utf16_len(o)},
+            },
+            "newText" : n,
+        }))
+        .collect()
+}
+
+//local path of a `file://` uri, percent escapes decoded
+fn uri_path(uri :&str) -> Option<PathBuf>
+{
+    let rest = uri.strip_prefix("file://")?.as_bytes();
+    let mut path : Vec<u8> = Vec::with_capacity(rest.len());
+    let mut i = 0;
+    while i < rest.len() {
+        let escaped = rest.get(i + 1..i + 3).and_then(|h|std::str::from_utf8(h).ok()).and_then(|h|u8::from_str_radix(h, 16).ok());
+        match (rest[i], escaped) {
+            (b'%', Some(b)) => { path.push(b); i += 3; },
+            (b, _) => { path.push(b); i += 1; },
+        }
+    }
+    String::from_utf8(path).ok().map(PathBuf::from)
+}
+
+type ConfigLookup = Box<dyn Fn(&Path) -> Result<Config, Box<dyn Error>>>;
+
+/// Formatting server speaking LSP over a pair of streams.
+/// Keeps the text of the open documents (full sync) and answers
+/// `textDocument/formatting` and `textDocument/rangeFormatting` with the edits of the changed lines.
+pub struct Server
+{
+    cfg : Config,
+    config_for : Option<ConfigLookup>,
+    docs : HashMap<String, String>,
+}
+
+impl Server
+{
+    pub fn new(cfg :Config) -> Self
+    {
+        Self{cfg, config_for : None, docs : HashMap::new()}
+    }
+
+    /// Config of the documents with a `file://` uri, e.g. from the profile found next to the file.
+    /// Other documents use the config given to `new`.
+    pub fn config_for(mut self, lookup :impl Fn(&Path) -> Result<Config, Box<dyn Error>> + 'static) -> Self
+    {
+        self.config_for = Some(Box::new(lookup));
+        self
+    }
+
+    fn uri(params :&Value) -> Option<&str>
+    {
+        params["textDocument"]["uri"].as_str()
+    }
+
+    //whole lines touched by the range, a range ending at the start of a line doesn't include it
+    fn lines_of(range :&Value) -> Option<LineRange>
+    {
+        let begin = range["start"]["line"].as_u64()? as usize;
+        let mut end = range["end"]["line"].as_u64()? as usize;
+        if end > begin && range["end"]["character"].as_u64() == Some(0) {
+            end -= 1;
+        }
+        Some(LineRange{begin : begin + 1, end : end.max(begin) + 1})
+    }
+
+    fn format(&self, params :&Value, range :Option<LineRange>) -> Result<Value, (i64, String)>
+    {
+        let uri = Self::uri(params).ok_or((INVALID_PARAMS, "missing textDocument.uri".to_string()))?;
+        let text = self.docs.get(uri).ok_or_else(||(INVALID_PARAMS, format!("unknown document: {}", uri)))?;
+        let line_count = text.split_inclusive('\n').count();
+        if line_count == 0 {
+            return Ok(json!([]));
+        }
+        let range = range.unwrap_or(LineRange{begin : 1, end : line_count});
+        if range.begin > line_count {
+            return Ok(json!([]));
+        }
+        let doc_cfg;
+        let cfg = match (&self.config_for, uri_path(uri)) {
+            (Some(lookup), Some(path)) => {
+                doc_cfg = lookup(&path).map_err(|e|(INTERNAL_ERROR, format!("{}: {}", path.display(), e)))?;
+                &doc_cfg
+            },
+            _ => &self.cfg,
+        };
+        let (res, _) = format_line_ranges(text, &[range], cfg).map_err(|e|(INTERNAL_ERROR, e.to_string()))?;
+        Ok(Value::Array(text_edits(text, &res)))
+    }
+
+    fn request(&mut self, method :&str, params :&Value) -> Result<Value, (i64, String)>
+    {
+        match method {
+            "initialize" => Ok(json!({
+                "capabilities" : {
+                    "textDocumentSync" : 1,
+                    "documentFormattingProvider" : true,
+                    "documentRangeFormattingProvider" : true,
+                },
+                "serverInfo" : {"name" : "format-columns"},
+            })),
+            "shutdown" => Ok(Value::Null),
+            "textDocument/formatting" => self.format(params, None),
+            "textDocument/rangeFormatting" => {
+                let range = Self::lines_of(&params["range"]).ok_or((INVALID_PARAMS, "invalid range".to_string()))?;
+                self.format(params, Some(range))
+            },
+            _ => Err((METHOD_NOT_FOUND, format!("unsupported method: {}", method))),
+        }
+    }
+
+    fn notification(&mut self, method :&str, params :&Value)
+    {
+        let uri = Self::uri(params).map(|u|u.to_string());
+        match (method, uri) {
+            ("textDocument/didOpen", Some(uri)) => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or("").to_string();
+                self.docs.insert(uri, text);
+            },
+            ("textDocument/didChange", Some(uri)) => {
+                //full sync: the last change holds the whole text
+                if let Some(text) = params["contentChanges"].as_array().and_then(|c|c.last()).and_then(|c|c["text"].as_str()) {
+                    self.docs.insert(uri, text.to_string());
+                }
+            },
+            ("textDocument/didClose", Some(uri)) => {
+                self.docs.remove(&uri);
+            },
+            _ => {}
+        }
+    }
+
+    /// Serves messages until `exit` or the end of the input
+    pub fn run<R :BufRead, W :Write>(&mut self, mut input :R, mut output :W) -> Result<(), Box<dyn Error>>
+    {
+        while let Some(msg) = read_message(&mut input)? {
+            let method = msg["method"].as_str().unwrap_or("");
+            if method == "exit" {
+                break;
+            }
+            let params = &msg["params"];
+            match msg.get("id") {
+                Some(id) => {
+                    let resp = match self.request(method, params) {
+                        Ok(result) => json!({"jsonrpc" : "2.0", "id" : id, "result" : result}),
+                        Err((code, message)) => json!({"jsonrpc" : "2.0", "id" : id, "error" : {"code" : code, "message" : message}}),
+                    };
+                    write_message(&mut output, &resp)?;
+                },
+                None => self.notification(method, params),
+            }
+        }
+        Ok(())
+    }
+}
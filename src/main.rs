@@ -1,171 +1,248 @@
-#[macro_use] 
-extern crate lazy_static;
-
-mod column_tools;
-mod analyzers;
-mod auto_config;
-mod tests;
-
 use std::error::Error;
-    
-use analyzers::LineAnalyzer;
-
-use analyzers::assignment::TypeVarAnalyzer as AssignmentAnalyzer;
-use analyzers::assignment::VarAnalyzer as AssignmentVarAnalyzer;
-use analyzers::func_decl::Analyzer as FuncDeclAnalyzer;
-use analyzers::xml_attr::Analyzer as XmlAttrAnalyzer;
-use analyzers::var_decl::Analyzer as VarDeclAnalyzer;
-use analyzers::bit_field::Analyzer as BitFieldAnalyzer;
-use analyzers::cmnt_struct::Analyzer as CommentStructAnalyzer;
-use analyzers::separators::Analyzer as SepLineAnalyzer;
-
-use column_tools::LineDescr;
-use column_tools::Printer;
-use column_tools::write_lines_into;
-
-use auto_config::auto_analyze;
-use auto_config::do_auto_config;
-
-use column_tools::Formatter;
+use std::io::Read;
+use std::path::Path;
+use std::path::PathBuf;
+
+use format_columns::Config;
+use format_columns::config::option_value;
+use format_columns::format_text_with_diagnostics;
+use format_columns::LineDiagnostic;
+use format_columns::auto_config::explain_auto;
+use format_columns::check::check_text;
+use format_columns::check::check_formatted;
+use format_columns::lines::LineRange;
+use format_columns::in_place::format_line_ranges;
+use format_columns::in_place::format_cursor_block;
+use format_columns::in_place::write_atomic;
+use format_columns::lsp::Server;
+use format_columns::streaming::format_stream;
+use format_columns::streaming::format_stream_spooled;
+use format_columns::profiles::find_config_file;
+use format_columns::profiles::load_profile;
+use format_columns::profiles::CONFIG_FILE_NAME;
+
+//exit code of --check when formatting would change the input, errors exit with 1
+const CHECK_FAILED : i32 = 2;
+
+fn read_input(src_file:Option<&String>)->Result<String, Box<dyn Error>>
+{
+    let mut text = String::new();
+    match src_file {
+        Some(f) => {
+            text = std::fs::read_to_string(f).map_err(|e|format!("{}: {}", f, e))?;
+        },
+        None => {
+            std::io::stdin().read_to_string(&mut text)?;
+        }
+    }
+    Ok(text)
+}
 
-type DynLineAnalyzer = Box<dyn LineAnalyzer>;
-type ACreate = Box<dyn Fn()->DynLineAnalyzer>;
-type AnalyzerFactory = std::collections::HashMap<&'static str, ACreate>;
+//explicit --config or the file found next to the input (or the current directory for stdin)
+fn config_file(explicit :Option<&String>, src_file :Option<&Path>) -> Option<PathBuf>
+{
+    if let Some(p) = explicit {
+        return Some(PathBuf::from(p));
+    }
+    let start_dir = match src_file.and_then(|f|f.parent()) {
+        Some(d) if !d.as_os_str().is_empty() => d.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    find_config_file(&std::fs::canonicalize(start_dir).ok()?)
+}
 
-    
-fn read_input(src_file:Option<&String>)->(Vec<String>, Option<String>)
+//profile from the config file first, so that the command line overrides it
+//the language server detects the mode unless told otherwise
+fn load_config(args :&[String], lsp :bool, config_path :Option<&String>, profile :Option<&String>, src_file :Option<&Path>) -> Result<Config, Box<dyn Error>>
 {
-    let mut src : Box<dyn std::io::BufRead> = if src_file.is_some() {
-            let f = std::fs::File::open(src_file.unwrap());
-            if f.is_ok() {
-                Box::new(std::io::BufReader::new(f.unwrap()))
-            }else{
-                Box::new(std::io::BufReader::new(std::io::stdin()))
-            }
-        }else{
-            Box::new(std::io::BufReader::new(std::io::stdin()))
-        };
+    let mut cfg = if lsp { Config::new().auto() } else { Config::new() };
+    match config_file(config_path, src_file) {
+        Some(path) => cfg.parse_args(load_profile(&path, profile.map(|p|p.as_str()))?.iter())?,
+        None => if let Some(p) = profile {
+            return Err(format!("profile '{}' requested, but no {} found", p, CONFIG_FILE_NAME).into());
+        },
+    }
+    cfg.parse_args(args.iter())?;
+    Ok(cfg)
+}
 
-    let mut first_string : Option<String> = None;
-    let mut lines_str : Vec<String> = vec![];
-    loop  
-    {
-        let mut l :String = String::new();
-        if src.read_line(&mut l).unwrap() <= 0 {
-            break;
-        } 
-        if first_string.is_none() {
-            first_string = Some(l.clone());
+//line, position marker and reason for every line that was not aligned
+fn print_diagnostics(lines :&[&str], diags :&[LineDiagnostic])
+{
+    for d in diags.iter() {
+        eprintln!("line {}: {}", d.line, d.issue);
+        let text = lines.get(d.line - 1).copied().unwrap_or("");
+        eprintln!("    {}", text);
+        if let Some(off) = d.offset() {
+            let col = text.get(..off).map(|t|t.chars().count()).unwrap_or(0);
+            eprintln!("    {}^", " ".repeat(col));
         }
-        lines_str.push(l.trim_end().to_string());
     }
-    
-    (lines_str, first_string)
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    
-    let factory : AnalyzerFactory = {
-        let mut factory : AnalyzerFactory = AnalyzerFactory::new();
-        factory.insert("sep"           , Box::new(||Box::new(SepLineAnalyzer::new()      )));
-        factory.insert("bit_field"     , Box::new(||Box::new(BitFieldAnalyzer{}          )));
-        factory.insert("comment_struct", Box::new(||Box::new(CommentStructAnalyzer::new())));
-        factory.insert("var_decl"      , Box::new(||Box::new(VarDeclAnalyzer{}           )));
-        factory.insert("xml"           , Box::new(||Box::new(XmlAttrAnalyzer{}           )));
-        factory.insert("func_decl"     , Box::new(||Box::new(FuncDeclAnalyzer{}          )));
-        factory.insert("assign_var"    , Box::new(||Box::new(AssignmentVarAnalyzer{}     )));
-        factory.insert("assign_init"   , Box::new(||Box::new(AssignmentAnalyzer{}        )));
-        factory
-    };
+//error for the first set option that `mode` can't be combined with
+fn reject_conflicts(mode :&str, conflicts :&[(bool, &str)]) -> Result<(), Box<dyn Error>>
+{
+    match conflicts.iter().find(|(set, _)|*set) {
+        Some((_, opt)) => Err(format!("{} can't be combined with {}", mode, opt).into()),
+        None => Ok(()),
+    }
+}
 
-    let mut analyzer_type : String = String::from("sep");
+fn main() -> Result<(), Box<dyn Error>> {
     let args : Vec<String> = std::env::args().collect();
 
-    let mut lines: Vec<LineDescr> = Vec::new();
-    
     let mut out_file : Option<&String>  = None;
     let mut src_file : Option<&String>  = None;
-
-    let mut auto_config = false;
     let mut type_only = false;
+    let mut explain = false;
+    let mut diagnostics = false;
+    let mut check = false;
+    let mut in_place = false;
+    let mut lsp = false;
+    let mut stream = false;
+    let mut cursor_line : Option<usize> = None;
+    let mut backup_suffix : Option<&String> = None;
+    let mut ranges : Vec<LineRange> = Vec::new();
+    let mut profile : Option<&String> = None;
+    let mut config_path : Option<&String> = None;
 
     let mut arg_it = args.iter();
-    loop 
-    {
-        if let Some(arg) = arg_it.next() {
-           if arg == "--file" {
-               src_file = arg_it.next();
-           }else if arg == "--out" {
-               out_file = arg_it.next();
-           }else if arg == "--analyzer" {
-               if let Some(atype) = arg_it.next() {
-                    let rstr : &str = &*atype;
-                    if factory.contains_key(&rstr) {
-                        analyzer_type = atype.clone();
-                    }
-               }
-           }else if arg == "--auto" {
-               auto_config = true;
-           }else if arg == "--type" {
-               type_only = true;
-           } 
-        }else {
-            break;
-        }
-    };
-
-    let (lines_str, first_string) = read_input(src_file);
-
-    let mut line_analyzer : DynLineAnalyzer;
-    let mut printer;
-    let mut fmtr;
+    while let Some(arg) = arg_it.next() {
+       if arg == "--file" {
+           src_file = Some(option_value(arg, &mut arg_it)?);
+       }else if arg == "--out" {
+           out_file = Some(option_value(arg, &mut arg_it)?);
+       }else if arg == "--type" {
+           type_only = true;
+       }else if arg == "--explain-auto" || arg == "--explain_auto" {
+           explain = true;
+       }else if arg == "--diagnostics" || arg == "--verbose" {
+           diagnostics = true;
+       }else if arg == "--check" {
+           check = true;
+       }else if arg == "--in-place" || arg == "--in_place" {
+           in_place = true;
+       }else if arg == "--cursor_line" || arg == "--cursor-line" {
+           let l = option_value(arg, &mut arg_it)?;
+           cursor_line = Some(l.parse::<usize>().map_err(|e|format!("--cursor_line {}: {}", l, e))?);
+       }else if arg == "--stream" {
+           stream = true;
+       }else if arg == "--lsp" {
+           lsp = true;
+       }else if arg == "--backup" {
+           backup_suffix = Some(option_value(arg, &mut arg_it)?);
+       }else if arg == "--lines" {
+           let r = option_value(arg, &mut arg_it)?;
+           ranges.push(r.parse::<LineRange>().map_err(|e|format!("--lines {}: {}", r, e))?);
+       }else if arg == "--profile" {
+           profile = Some(option_value(arg, &mut arg_it)?);
+       }else if arg == "--config" {
+           config_path = Some(option_value(arg, &mut arg_it)?);
+       }
+    }
 
+    if lsp {
+        //documents come from the client, edits go back to it
+        reject_conflicts("--lsp", &[(check, "--check"), (in_place, "--in-place"), (stream, "--stream"), (!ranges.is_empty(), "--lines"),
+            (src_file.is_some(), "--file"), (out_file.is_some(), "--out"), (cursor_line.is_some(), "--cursor_line"),
+            (explain, "--explain-auto"), (type_only, "--type"), (diagnostics, "--diagnostics")])?;
+    }
+    let cfg = load_config(&args, lsp, config_path, profile, src_file.map(Path::new))?;
+    if lsp {
+        //every document gets the profile found next to it
+        let (args, config_path, profile) = (args.clone(), config_path.cloned(), profile.cloned());
+        return Server::new(cfg)
+            .config_for(move |path|load_config(&args, true, config_path.as_ref(), profile.as_ref(), Some(path)))
+            .run(std::io::stdin().lock(), std::io::stdout().lock());
+    }
+    if stream {
+        //streaming only writes the formatted text, the other modes need the whole input
+        reject_conflicts("--stream", &[(in_place, "--in-place"), (check, "--check"), (cursor_line.is_some(), "--cursor_line"), (!ranges.is_empty(), "--lines"),
+            (explain, "--explain-auto"), (type_only, "--type"), (diagnostics, "--diagnostics")])?;
+        //two passes over the file, stdin is spooled to a temporary file
+        let mut out : Box<dyn std::io::Write> = match out_file {
+                Some(f) => Box::new(std::io::BufWriter::new(std::fs::File::create(f).map_err(|e|format!("{}: {}", f, e))?)),
+                None => Box::new(std::io::BufWriter::new(std::io::stdout().lock())),
+            };
+        return match src_file {
+            Some(f) => {
+                let file = std::fs::File::open(f).map_err(|e|format!("{}: {}", f, e))?;
+                format_stream(std::io::BufReader::new(file), &mut out, &cfg)
+            },
+            None => format_stream_spooled(std::io::stdin().lock(), &mut out, &cfg),
+        };
+    }
+    if check && in_place {
+        return Err("--check can't be combined with --in-place".into());
+    }
+    if in_place && src_file.is_none() {
+        return Err("--in-place needs --file".into());
+    }
 
-    if auto_config && first_string.is_some() {
-        let fs = first_string.unwrap();
-        let auto_config_res = do_auto_config(auto_analyze(&fs));
-        line_analyzer = auto_config_res.analyzer;
-        fmtr = auto_config_res.formatter;
-        printer = auto_config_res.printer;
-    }else
-    {
-        let entry = factory.get_key_value(analyzer_type.as_str()).unwrap();
-        line_analyzer = entry.1();
-        line_analyzer.parse_args(args.iter())?;
+    let text = read_input(src_file)?;
 
-        printer = Printer::default();
-        printer.parse_args(args.iter())?;
-        
-        fmtr = Formatter::new();
-        fmtr.parse_args(args.iter())?;
+    let lines : Vec<&str> = text.lines().map(|l|l.trim_end()).collect();
+    if explain {
+        print!("{}", explain_auto(&lines));
+        return Ok(());
     }
 
     if type_only {
-        println!("{}", line_analyzer.type_name());
+        println!("{}", cfg.build(&lines)?.analyzer.type_name());
         return Ok(());
     }
-    
-    lines.reserve(lines_str.len());
-    lines_str.iter().for_each(|l|{
-       let mut line = LineDescr::new(&l);
-       fmtr.analyze_line(line_analyzer.as_mut(), &mut line);
-       lines.push(line); 
-    });
-    
-    fmtr.finish();
-    
-    let mut out : Box<dyn std::io::Write> = if out_file.is_some() {
-            let f = std::fs::File::create(out_file.unwrap());
-            if f.is_ok() {
-                Box::new(f?)
-            }else{
-                Box::new(std::io::stdout())
-            }
+
+    if check {
+        let name = src_file.map(|f|f.as_str()).unwrap_or("<stdin>");
+        //with a cursor line or line ranges only those lines are checked
+        let diff = if let Some(line) = cursor_line {
+            check_formatted(name, &text, &format_cursor_block(&text, line, &cfg)?.0)
+        }else if ranges.is_empty() {
+            check_text(name, &text, &cfg)?
         }else{
-                Box::new(std::io::stdout())
+            check_formatted(name, &text, &format_line_ranges(&text, &ranges, &cfg)?.0)
+        };
+        if let Some(diff) = diff {
+            print!("{}", diff);
+            std::process::exit(CHECK_FAILED);
+        }
+        return Ok(());
+    }
+
+    //in place the whole file is one range, so that line terminators are kept
+    if in_place && ranges.is_empty() && !lines.is_empty() {
+        ranges.push(LineRange{begin : 1, end : lines.len()});
+    }
+    let (res, diags) = if let Some(line) = cursor_line {
+        //the block goes to stderr, so that an editor can highlight it
+        let (res, block) = format_cursor_block(&text, line, &cfg)?;
+        eprintln!("{}:{}", block.begin, block.end);
+        (res, Vec::new())
+    }else if ranges.is_empty() {
+        format_text_with_diagnostics(&text, &cfg)?
+    }else{
+        format_line_ranges(&text, &ranges, &cfg)?
+    };
+    if diagnostics {
+        print_diagnostics(&lines, &diags);
+    }
+
+    if in_place {
+        if let Some(f) = src_file {
+            if res != text {
+                write_atomic(Path::new(f), &res, backup_suffix.map(|s|s.as_str()))?;
+            }
+        }
+        return Ok(());
+    }
+
+    let mut out : Box<dyn std::io::Write> = match out_file {
+            Some(f) => Box::new(std::fs::File::create(f).map_err(|e|format!("{}: {}", f, e))?),
+            None => Box::new(std::io::stdout()),
         };
 
-    printer.set_formatter(fmtr);
-    
-    write_lines_into(&lines, &printer, out.as_mut())
+    out.write_all(res.as_bytes())?;
+    out.flush()?;
+    Ok(())
 }
@@ -0,0 +1,88 @@
+use std::error::Error;
+use std::path::Path;
+use std::path::PathBuf;
+
+use toml::Table;
+use toml::Value;
+
+use crate::config::Config;
+
+pub const CONFIG_FILE_NAME : &str = ".format-columns.toml";
+
+/// Looks for the config file in `start_dir` and its parents
+pub fn find_config_file(start_dir :&Path) -> Option<PathBuf>
+{
+    start_dir.ancestors().map(|d|d.join(CONFIG_FILE_NAME)).find(|p|p.is_file())
+}
+
+fn value_to_arg(key :&str, v :&Value) -> Result<String, Box<dyn Error>>
+{
+    match v {
+        Value::String(s) => Ok(s.clone()),
+        Value::Integer(i) => Ok(i.to_string()),
+        Value::Float(f) => Ok(f.to_string()),
+        _ => Err(format!("unsupported value for '{}' in a profile", key).into()),
+    }
+}
+
+/// Turns a profile table into command line arguments, so that the same parsing applies to both.
+/// Keys are the option names without `--`, arrays repeat the option, `true` adds a flag without a value:
+///
+/// ```toml
+/// [profiles.struct]
+/// analyzer = "sep"
+/// seps = ",}"
+/// include = ["1{}"]
+/// sep_config = [",: :1"]
+/// prestart = true
+/// ```
+pub fn profile_to_args(profile :&Table) -> Result<Vec<String>, Box<dyn Error>>
+{
+    let mut args : Vec<String> = Vec::new();
+    for (key, v) in profile.iter() {
+        let opt = format!("--{}", key);
+        match v {
+            Value::Boolean(b) => if *b { args.push(opt); },
+            Value::Array(vals) => for item in vals.iter() {
+                args.push(opt.clone());
+                args.push(value_to_arg(key, item)?);
+            },
+            _ => {
+                args.push(opt);
+                args.push(value_to_arg(key, v)?);
+            }
+        }
+    }
+    Ok(args)
+}
+
+/// Arguments of the profile `name` from the config text, checked to be options of the config.
+/// Without a name the `default` profile is used if there is one.
+pub fn load_profile_str(text :&str, name :Option<&str>) -> Result<Vec<String>, Box<dyn Error>>
+{
+    let cfg = text.parse::<Table>()?;
+    let profiles = match cfg.get("profiles") {
+        Some(Value::Table(t)) => Some(t),
+        Some(_) => return Err("'profiles' must be a table".into()),
+        None => None,
+    };
+
+    let profile = profiles.and_then(|p|p.get(name.unwrap_or("default")));
+    match (profile, name) {
+        (Some(Value::Table(t)), _) => {
+            //a misspelled key would be ignored silently
+            let args = profile_to_args(t)?;
+            Config::new().parse_args_strict(args.iter())?;
+            Ok(args)
+        },
+        (Some(_), _) => Err(format!("profile '{}' must be a table", name.unwrap_or("default")).into()),
+        (None, Some(n)) => Err(format!("profile '{}' not found", n).into()),
+        (None, None) => Ok(Vec::new()),
+    }
+}
+
+pub fn load_profile(path :&Path, name :Option<&str>) -> Result<Vec<String>, Box<dyn Error>>
+{
+    let text = std::fs::read_to_string(path).map_err(|e|format!("{}: {}", path.display(), e))?;
+    load_profile_str(&text, name).map_err(|e|format!("{}: {}", path.display(), e).into())
+}
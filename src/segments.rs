@@ -0,0 +1,125 @@
+use regex::Regex;
+
+use crate::analyzers::LineAnalyzer;
+use crate::analyzers::LineParser;
+use crate::column_tools::Formatter;
+use crate::column_tools::ParseErr;
+use crate::column_tools::expanded_width;
+
+/// Strategies to split the input into independently aligned blocks
+#[derive(Clone)]
+pub enum Segmentation {
+    BlankLines,   //blank lines separate blocks and are kept as is
+    Indent,       //a change of indentation starts a new block
+    Regex(Regex), //lines matching the regex separate blocks and are kept as is
+    Analyzer,     //lines the analyzer can't accept separate blocks and are kept as is
+}
+
+impl std::str::FromStr for Segmentation {
+    type Err = ParseErr;
+
+    fn from_str(s :&str) -> Result<Self, Self::Err>
+    {
+       match s {
+           "blank" => Ok(Segmentation::BlankLines),
+           "indent" => Ok(Segmentation::Indent),
+           "analyzer" => Ok(Segmentation::Analyzer),
+           &_ => {
+               let re = s.strip_prefix("regex:").ok_or_else(||ParseErr::new("blank, indent, analyzer or regex:PATTERN", s))?;
+               Regex::new(re).map(Segmentation::Regex).map_err(|e|ParseErr{source : Some(Box::new(e)), ..ParseErr::new("valid regex", re).at(6)})
+           }
+       }
+    }
+}
+
+/// Consecutive lines that are aligned together, `format` is false for separator lines
+pub struct Segment
+{
+    pub begin : usize,
+    pub end : usize,
+    pub format : bool,
+}
+
+fn is_separator(l :&str, strategies :&[Segmentation], analyzer :&dyn LineAnalyzer, fmtr :&Formatter) -> bool
+{
+    strategies.iter().any(|st| match st {
+        Segmentation::BlankLines => l.trim().is_empty(),
+        Segmentation::Regex(re) => re.is_match(l),
+        Segmentation::Analyzer => {
+            let ignored = l.find_nwhite().map(|b|fmtr.check_line_start_to_ignore(&l[b..])).unwrap_or(false);
+            !ignored && analyzer.can_accept(l).is_err()
+        },
+        Segmentation::Indent => false,
+    })
+}
+
+fn indent_width(l :&str, tab_width :usize) -> Option<usize>
+{
+    l.find_nwhite().ok().map(|b|expanded_width(&l[..b], tab_width))
+}
+
+/// Role of a line in the segmentation
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum LineRole
+{
+    Separator, //kept as is, ends the current segment
+    Start,     //first line of a new segment
+    Continue,  //line of the current segment
+}
+
+/// Incremental form of `split_segments` for lines coming one by one
+pub struct Segmenter<'s>
+{
+    strategies : &'s [Segmentation],
+    by_indent : bool,
+    indent : Option<usize>,
+    in_segment : bool,
+}
+
+impl<'s> Segmenter<'s>
+{
+    pub fn new(strategies :&'s [Segmentation]) -> Self
+    {
+        let by_indent = strategies.iter().any(|st|matches!(st, Segmentation::Indent));
+        Self{strategies, by_indent, indent : None, in_segment : false}
+    }
+
+    pub fn next_line(&mut self, l :&str, analyzer :&dyn LineAnalyzer, fmtr :&Formatter) -> LineRole
+    {
+        if is_separator(l, self.strategies, analyzer, fmtr) {
+            self.in_segment = false;
+            self.indent = None;
+            return LineRole::Separator;
+        }
+
+        let mut new_segment = !self.in_segment;
+        if self.by_indent {
+            //blank lines don't change the indentation of the block
+            if let Some(w) = indent_width(l, fmtr.tab_width()) {
+                new_segment = new_segment || self.indent.is_some_and(|prev|prev != w);
+                self.indent = Some(w);
+            }
+        }
+        self.in_segment = true;
+        if new_segment { LineRole::Start } else { LineRole::Continue }
+    }
+}
+
+/// Splits the lines into segments, without strategies the whole input is one segment
+pub fn split_segments(lines :&[&str], strategies :&[Segmentation], analyzer :&dyn LineAnalyzer, fmtr :&Formatter) -> Vec<Segment>
+{
+    let mut splitter = Segmenter::new(strategies);
+    let mut res : Vec<Segment> = Vec::new();
+
+    for (i, l) in lines.iter().enumerate() {
+        match splitter.next_line(l, analyzer, fmtr) {
+            LineRole::Separator => res.push(Segment{begin : i, end : i + 1, format : false}),
+            LineRole::Start => res.push(Segment{begin : i, end : i + 1, format : true}),
+            LineRole::Continue => if let Some(s) = res.last_mut() {
+                s.end = i + 1;
+            },
+        }
+    }
+    res
+}
@@ -0,0 +1,178 @@
+use std::error::Error;
+use std::io::BufRead;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use crate::analyzers::LineAnalyzer;
+use crate::config::Config;
+use crate::column_tools::Formatter;
+use crate::column_tools::LineDescr;
+use crate::column_tools::Printer;
+use crate::comments::CommentColumn;
+use crate::comments::CommentMarker;
+use crate::comments::append_comment;
+use crate::comments::shared_column;
+use crate::comments::split_trailing_comment;
+use crate::segments::LineRole;
+use crate::segments::Segmenter;
+
+/// Lines used to detect the mode in auto mode, the rest of the input is not kept in memory
+pub const AUTO_SAMPLE_LINES : usize = 1000;
+
+//reads the next line into `buf` without the terminator, the trailing whitespace is kept for lines going on inside a cell
+fn next_line<R :BufRead>(input :&mut R, buf :&mut String) -> Result<bool, Box<dyn Error>>
+{
+    buf.clear();
+    if input.read_line(buf)? == 0 {
+        return Ok(false);
+    }
+    buf.truncate(buf.trim_end_matches(['\n', '\r']).len());
+    Ok(true)
+}
+
+//code of the line and its trailing comment if comments are aligned
+fn split_line<'a>(l :&'a str, markers :Option<&[CommentMarker]>) -> (&'a str, Option<&'a str>)
+{
+    match markers {
+        Some(m) => split_trailing_comment(l, m),
+        None => (l, None),
+    }
+}
+
+//reads the input from its current position, analyzes the lines with a scratch formatter and formats them with the widths of their segment;
+//`emit` gets the index of the segment (None for separator lines), the formatted code and the trailing comment
+fn format_pass<R, F>(input :&mut R, analyzer :&mut dyn LineAnalyzer, printer :&mut Printer, widths :Vec<Formatter>,
+    template :&Formatter, cfg :&Config, mut emit :F) -> Result<(), Box<dyn Error>>
+where R :BufRead, F :FnMut(Option<usize>, String, Option<&str>) -> Result<(), Box<dyn Error>>
+{
+    let comments = cfg.trailing_comments();
+    let markers = comments.as_ref().map(|(_, m)|m.as_slice());
+    analyzer.reset_state();
+    let mut buf = String::new();
+    let mut widths = widths.into_iter();
+    let mut seg_idx : Option<usize> = None;
+    let mut scratch = template.clone();
+    let mut segmenter = Segmenter::new(cfg.segmentations());
+    while next_line(input, &mut buf)? {
+        let (code, comment) = split_line(buf.trim_end(), markers);
+        match segmenter.next_line(code, analyzer, template) {
+            LineRole::Separator => {
+                emit(None, buf.trim_end().to_string(), None)?;
+                continue;
+            },
+            LineRole::Start => {
+                printer.set_formatter(widths.next().ok_or("input changed between the passes")?);
+                seg_idx = Some(seg_idx.map_or(0, |i|i + 1));
+            },
+            LineRole::Continue => {},
+        }
+        scratch.reset_columns();
+        let mut line = LineDescr::new(code);
+        let _ = scratch.analyze_line(analyzer, &mut line);
+        if let Some(mut s) = printer.format_line(&line) {
+            if line.continues() {
+                s.push_str(&buf[buf.trim_end().len()..]);
+            }
+            emit(seg_idx, s, comment)?;
+        }
+    }
+    Ok(())
+}
+
+/// Formats the input in two passes without keeping it in memory:
+/// the first pass only collects the column widths of every segment,
+/// the second one reads the input again and writes the lines as soon as they are formatted.
+/// Trailing comments aligned to a shared column take one more pass in between to measure the formatted lines.
+/// The output is the same as of `format_text`, except that auto mode only looks at the first `AUTO_SAMPLE_LINES` lines.
+pub fn format_stream<R :BufRead + Seek, W :Write>(mut input :R, out :&mut W, cfg :&Config) -> Result<(), Box<dyn Error>>
+{
+    let start = input.stream_position()?;
+    let mut buf = String::new();
+    let comments = cfg.trailing_comments();
+    let markers = comments.as_ref().map(|(_, m)|m.as_slice());
+
+    let mut sample : Vec<String> = Vec::new();
+    while sample.len() < AUTO_SAMPLE_LINES && next_line(&mut input, &mut buf)? {
+        sample.push(split_line(buf.trim_end(), markers).0.to_string());
+    }
+    let setup = {
+        let sample_str : Vec<&str> = sample.iter().map(|l|l.as_str()).collect();
+        cfg.build(&sample_str)?
+    };
+    drop(sample);
+    let mut analyzer = setup.analyzer;
+    let mut printer = setup.printer;
+    let template = setup.formatter;
+
+    //first pass: widths of every segment
+    input.seek(SeekFrom::Start(start))?;
+    let mut widths : Vec<Formatter> = Vec::new();
+    let mut segmenter = Segmenter::new(cfg.segmentations());
+    while next_line(&mut input, &mut buf)? {
+        let (code, _) = split_line(buf.trim_end(), markers);
+        match segmenter.next_line(code, analyzer.as_ref(), &template) {
+            LineRole::Separator => continue,
+            LineRole::Start => widths.push(template.clone()),
+            LineRole::Continue => {},
+        }
+        if let Some(fmtr) = widths.last_mut() {
+            let mut line = LineDescr::new(code);
+            let _ = fmtr.analyze_line(analyzer.as_mut(), &mut line);
+        }
+    }
+    widths.iter_mut().for_each(|f|f.finish());
+
+    //shared comment columns need the formatted lines, they are measured in a pass of their own
+    let tw = template.tab_width();
+    let mut columns : Vec<usize> = Vec::new();
+    if let Some((CommentColumn::Shared, _)) = &comments {
+        columns = vec![0; widths.len()];
+        input.seek(SeekFrom::Start(start))?;
+        format_pass(&mut input, analyzer.as_mut(), &mut printer, widths.clone(), &template, cfg, |seg, l, comment|{
+            if let (Some(seg), Some(_)) = (seg, comment) {
+                columns[seg] = columns[seg].max(shared_column(&l, tw));
+            }
+            Ok(())
+        })?;
+    }
+
+    //second pass: the lines are printed as soon as they are formatted
+    let mut first = true;
+    input.seek(SeekFrom::Start(start))?;
+    format_pass(&mut input, analyzer.as_mut(), &mut printer, widths, &template, cfg, |seg, mut l, comment|{
+        if !first {
+            out.write_all(b"\n")?;
+        }
+        first = false;
+        if let (Some(seg), Some(c), Some((column, _))) = (seg, comment, &comments) {
+            let at = match column {
+                CommentColumn::Fixed(n) => *n,
+                CommentColumn::Shared => columns[seg],
+            };
+            append_comment(&mut l, c, at, tw);
+        }
+        out.write_all(l.as_bytes())?;
+        Ok(())
+    })?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Same as `format_stream` for input that can't be read twice (like stdin):
+/// the input is copied to a temporary file during the first read
+pub fn format_stream_spooled<R :std::io::Read, W :Write>(mut input :R, out :&mut W, cfg :&Config) -> Result<(), Box<dyn Error>>
+{
+    static SPOOLS : AtomicUsize = AtomicUsize::new(0);
+    let path = std::env::temp_dir().join(format!("format-columns-spool-{}-{}", std::process::id(), SPOOLS.fetch_add(1, Ordering::Relaxed)));
+    let res = (|| -> Result<(), Box<dyn Error>> {
+        let mut spool = std::fs::OpenOptions::new().read(true).write(true).create_new(true).open(&path)?;
+        std::io::copy(&mut input, &mut spool)?;
+        spool.seek(SeekFrom::Start(0))?;
+        format_stream(std::io::BufReader::new(spool), out, cfg)
+    })();
+    let _ = std::fs::remove_file(&path);
+    res
+}
@@ -7,6 +7,29 @@ mod test_cmnt_structs;
 mod test_separators;
 mod test_auto_detect;
 mod test_func_call;
+mod test_config;
+mod test_unicode;
+mod test_column_style;
+mod test_numeric;
+mod test_tabs;
+mod test_segments;
+mod test_profiles;
+mod test_errors;
+mod test_check;
+mod test_in_place;
+mod test_lsp;
+mod test_cursor;
+mod test_indent;
+mod test_streaming;
+mod test_width_budget;
+mod test_wrap;
+mod test_pipe_table;
+mod test_csv;
+mod test_unalign;
+mod test_rust_decl;
+mod test_match_arm;
+mod test_comments;
+mod test_continuation;
 
 #[cfg(test)]
 pub mod mytests {
@@ -20,12 +43,11 @@ pub mod mytests {
     pub fn run_analyzer(in_s :&str, la :&mut dyn LineAnalyzer, mut fmtr :Formatter, mut printer :Printer)->String{
        let lines_str : Vec<String> = in_s.lines().map(|x|x.to_string()).collect();
 
-       let mut lines: Vec<LineDescr> = Vec::new();
+       let mut lines: Vec<LineDescr> = Vec::with_capacity(lines_str.len());
 
-        lines.reserve(lines_str.len());
         lines_str.iter().for_each(|l|{
-           let mut line = LineDescr::new(&l);
-           fmtr.analyze_line(la, &mut line);
+           let mut line = LineDescr::new(l);
+           let _ = fmtr.analyze_line(la, &mut line);
            lines.push(line); 
         });
 
@@ -78,7 +100,7 @@ pub mod mytests {
         let exp_lines = expected.lines();
         
         let mut line_idx = 0;
-        let cmp_lines = res_lines.into_iter().zip(exp_lines.into_iter());
+        let cmp_lines = res_lines.into_iter().zip(exp_lines);
         cmp_lines.for_each(|(res, exp)|{
             let d = cmp_str(res, exp);
             assert!(d == -1, "Diff at line {0}.\nResult:\n{1}|\n{cur:>pad$}\nExpected:\n{2}|\n{cur:>pad$}", line_idx, res, exp, pad = (d + 1) as usize, cur = '^');
@@ -19,11 +19,11 @@ mod mytests {
         
         //expected:
         let out_str = r##"
-      MyClass          x   = 12;           
-      int              x   = 155;          
+      MyClass          x   = 12;
+      int              x   = 155;
       SomwOtherl       vay = SomwOtherl(x);
-      const char *     x   = "xxxxx";      
-      std::span<int,3> s   = 2;            
+      const char *     x   = "xxxxx";
+      std::span<int,3> s   = 2;
     "##;
 
         let result = run_analyzer(in_str, cfg.analyzer.as_mut(), cfg.formatter, cfg.printer);
@@ -46,11 +46,11 @@ s = 2;
         
         //expected:
         let out_str = r##"
-MyClass    = 12;           
-int        = 155;          
+MyClass    = 12;
+int        = 155;
 SomwOtherl = SomwOtherl(x);
-char       = "xxxxx";      
-s          = 2;            
+char       = "xxxxx";
+s          = 2;
     "##;
 
         let result = run_analyzer(in_str, cfg.analyzer.as_mut(), cfg.formatter, cfg.printer);
@@ -54,4 +54,27 @@ mod mytests {
         let mode = auto_analyze(r##"{"SomeOtherApi::CoolMethod", (int64_t)CueOnly::Test},"##);
         assert_eq!(mode, AutoMode::CLike(Some('{'), Some('}')));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_lines() {
+        //first line alone would be detected as SimpleSpace
+        let mode = auto_analyze_lines(&["// some comment", "int a = 1;", "long bb = 2;"]);
+        assert_eq!(mode, AutoMode::SimpleAssignment);
+
+        //optional trailing comment doesn't make the column count inconsistent
+        let mode = auto_analyze_lines(&["uint64_t somebit : 1;", "uint64_t verylongbi : 4; //and here's a comment", "uint64_t sho : 15;"]);
+        assert_eq!(mode, AutoMode::BitField);
+
+        //most of the lines are calls
+        let mode = auto_analyze_lines(&["x = 5;", "some_func(int, float);", "other_func(a, b);"]);
+        assert_eq!(mode, AutoMode::FnCall);
+
+        let mode = auto_analyze_lines(&[]);
+        assert_eq!(mode, AutoMode::SimpleSpace);
+
+        let explain = explain_auto(&["int a = 1;", "long bb = 2;"]);
+        assert!(explain.starts_with("rank"));
+        assert!(explain.contains("picked SimpleAssignment: 2 of 2 lines accepted"));
+        assert!(explain.contains("by priority"));
+    }
+}
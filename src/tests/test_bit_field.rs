@@ -18,10 +18,10 @@ mod mytests {
         
         //expected:
         let out_str = r##"
-    uint64_t somebit     : 1; 
+    uint64_t somebit     : 1;
     uint64_t verylongbi  : 4;  //and here's a comment
     uint64_t sho         : 15;
-    uint64_t b           : 2;  //some other comments 
+    uint64_t b           : 2;  //some other comments
     uint64_t and_another : 10;"##;
 
         let result = run_analyzer(in_str, cfg.analyzer.as_mut(), cfg.formatter, cfg.printer);
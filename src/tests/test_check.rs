@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod mytests {
+    use crate::tests::mytests::assert_eq;
+    use crate::check::check_text;
+    use crate::check::unified_diff;
+    use crate::check::check_formatted;
+    use crate::in_place::format_line_ranges;
+    use crate::lines::LineRange;
+    use crate::config::Config;
+    use crate::column_tools::Align;
+
+    #[test]
+    fn test_diff() {
+        let old = vec!["a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l"];
+        let new = vec!["a", "B", "c", "d", "e", "f", "g", "h", "i", "j", "K", "l"];
+
+        //expected:
+        let out_str = r##"--- f.txt
++++ f.txt
+@@ -1,5 +1,5 @@
+ a
+-b
++B
+ c
+ d
+ e
+@@ -8,5 +8,5 @@
+ h
+ i
+ j
+-k
++K
+ l
+"##;
+        assert_eq(&unified_diff("f.txt", &old, &new, 3), out_str);
+        assert!(unified_diff("f.txt", &old, &old, 3).is_empty());
+    }
+
+    #[test]
+    fn test_check() {
+        let cfg = Config::new().separators(vec![',']).align(Align::Left);
+
+        //input
+        let in_str = "a,b\nccc,d\n\n";
+        
+        //expected:
+        let out_str = r##"--- in
++++ in
+@@ -1,3 +1,3 @@
+-a,b
++a  ,b
+ ccc,d
+ 
+"##;
+        assert_eq(&check_text("in", in_str, &cfg).unwrap().unwrap(), out_str);
+        assert!(check_text("in", "a  ,b\nccc,d\n\n", &cfg).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_check_ranges() {
+        let cfg = Config::new().separators(vec![',']).align(Align::Left);
+
+        //input
+        let in_str = "a,b\nccc,d\n\ne,f\ngg,h\n";
+
+        //expected: only the second block is checked
+        let out_str = r##"--- in
++++ in
+@@ -1,5 +1,5 @@
+ a,b
+ ccc,d
+ 
+-e,f
++e ,f
+ gg,h
+"##;
+        let (formatted, _) = format_line_ranges(in_str, &[LineRange{begin : 4, end : 5}], &cfg).unwrap();
+        assert_eq(&check_formatted("in", in_str, &formatted).unwrap(), out_str);
+        assert!(check_formatted("in", in_str, in_str).is_none());
+    }
+
+    #[test]
+    fn test_check_wrap() {
+        let cfg = Config::new().separators(vec![',']).align(Align::Left).wrap(20);
+
+        //input
+        let in_str = "a , x\nb , y\nbb, some prose that goes on and on\nc , y\nd , z\ne , w\nf , v\ng , u\n";
+
+        //expected: the added line doesn't shift the comparison of the following ones
+        let out_str = r##"--- in
++++ in
+@@ -1,6 +1,7 @@
+ a , x
+ b , y
+-bb, some prose that goes on and on
++bb, some prose that
++    goes on and on
+ c , y
+ d , z
+ e , w
+"##;
+        assert_eq(&check_text("in", in_str, &cfg).unwrap().unwrap(), out_str);
+    }
+
+    #[test]
+    fn test_diff_insert() {
+        let old = vec!["a", "b", "c"];
+        let new = vec!["a", "b", "x", "c", "y"];
+
+        //expected:
+        let out_str = r##"--- f
++++ f
+@@ -1,3 +1,5 @@
+ a
+ b
++x
+ c
++y
+"##;
+        assert_eq(&unified_diff("f", &old, &new, 3), out_str);
+    }
+}
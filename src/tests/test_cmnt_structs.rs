@@ -16,7 +16,7 @@ mod mytests {
 /* empty */ {"OneMore::WhoNeedsThis", &OneMore::WhoNeedsThis, "nothing", ""},
     "##;
         
-        //expected: (currently there are spaces at the end of each line except the longest one)
+        //expected:
         let out_str = r##"
 /* some hint         */ {"SomeApi::Func1"          , &SomeApi::Func1            , "{int b[, int : a]}"                 , "result: boolean"},
 /* bla               */ {"SomeOtherApi::CoolMethod", &SomeOtherApi::CoolMyMethod, "string : nu, int : bla[, {x : all}]", "nothing"        },
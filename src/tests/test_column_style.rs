@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod mytests {
+    use crate::tests::mytests::run_analyzer;
+    use crate::tests::mytests::assert_eq;
+    use crate::auto_config::*;
+    use crate::config::Config;
+    use crate::column_tools::Align;
+    use crate::column_tools::ColumnStyle;
+    use crate::format_text;
+
+    #[test]
+    fn test_parse() {
+        assert!("2:align=right,fill=.,min=8".parse::<ColumnStyle>().is_ok());
+        assert!("-1:sep_count=2,sep_align=right".parse::<ColumnStyle>().is_ok());
+        assert!("x:align=right".parse::<ColumnStyle>().is_err());
+        assert!("1:align=up".parse::<ColumnStyle>().is_err());
+        assert!("1:width=2".parse::<ColumnStyle>().is_err());
+    }
+
+    #[test]
+    fn test_comma() {
+        let mut cfg = do_auto_config(AutoMode::SimpleComma);
+        cfg.printer.add_column_styles(vec![
+            "1:align=right".parse::<ColumnStyle>().unwrap(),
+            "-1:fill=.,min=8".parse::<ColumnStyle>().unwrap(),
+        ]);
+        cfg.printer.set_rectangular(true);
+
+        //input
+        let in_str = r##"
+some weird, data, 1
+in, columns, 22
+try to, format it, 333"##;
+        
+        //expected:
+        let out_str = r##"
+some weird,      data, 1.......
+in        ,   columns, 22......
+try to    , format it, 333....."##;
+
+        let result = run_analyzer(in_str, cfg.analyzer.as_mut(), cfg.formatter, cfg.printer);
+
+        assert_eq(&result, out_str);
+    }
+
+    #[test]
+    fn test_separator() {
+        let cfg = Config::new()
+            .separators(vec![','])
+            .align(Align::Left)
+            .column_style("2:sep_count=2,sep_align=right".parse::<ColumnStyle>().unwrap());
+
+        let result = format_text("a,b,c,d\neee,f,g,h", &cfg).unwrap();
+        assert_eq(&result, "a  ,b,c  ,d\neee,f,g  ,h");
+    }
+
+    #[test]
+    fn test_merge() {
+        //a negative index hitting the same column adds its fields
+        let cfg = Config::new().separators(vec![',']).align(Align::Left)
+            .column_style("1:align=right,min=4,sep_count=2,sep_align=right".parse::<ColumnStyle>().unwrap())
+            .column_style("-2:fill=.".parse::<ColumnStyle>().unwrap());
+
+        let result = format_text("a,b,c\neee,f,g", &cfg).unwrap();
+        assert_eq(&result, "a  ,...b  ,c\neee,...f  ,g");
+
+        //later fields win
+        let cfg = Config::new().separators(vec![',']).align(Align::Left)
+            .column_style("1:align=right,min=4,sep_count=2,sep_align=right".parse::<ColumnStyle>().unwrap())
+            .column_style("-2:fill=.,align=left".parse::<ColumnStyle>().unwrap());
+        let result = format_text("a,b,c\neee,f,g", &cfg).unwrap();
+        assert_eq(&result, "a  ,b...  ,c\neee,f...  ,g");
+    }
+}
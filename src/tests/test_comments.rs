@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod mytests {
+    use crate::tests::mytests::assert_eq;
+    use crate::config::Config;
+    use crate::column_tools::Align;
+    use crate::comments::CommentColumn;
+    use crate::comments::CommentMarker;
+    use crate::segments::Segmentation;
+    use crate::streaming::format_stream;
+    use crate::format_text;
+
+    #[test]
+    fn test_shared_column() {
+        let cfg = Config::new().auto().align_comments(CommentColumn::Shared);
+
+        //input
+        let in_str = r##"int a = 1; // one
+long bbb = 22;   /* two */
+char c = '/'; // "quoted"
+const char* s = "a // b"; // real
+// just a comment
+x = 3;"##;
+
+        //expected:
+        let out_str = r##"int   a       = 1;        // one
+long  bbb     = 22;       /* two */
+char  c       = '/';      // "quoted"
+const char* s = "a // b"; // real
+// just a comment
+x             = 3;"##;
+
+        assert_eq(&format_text(in_str, &cfg).unwrap(), out_str);
+    }
+
+    #[test]
+    fn test_fixed_column() {
+        let cfg = Config::new().auto().align_comments(CommentColumn::Fixed(18));
+
+        //input
+        let in_str = r##"    a = 1; // one
+    bbb = 22; // two
+    cccccc = "long value"; // three"##;
+
+        //expected:
+        let out_str = r##"    a      = 1;   // one
+    bbb    = 22;  // two
+    cccccc = "long value"; // three"##;
+
+        assert_eq(&format_text(in_str, &cfg).unwrap(), out_str);
+    }
+
+    #[test]
+    fn test_markers() {
+        let cfg = Config::new().separators(vec![' ']).align(Align::Left)
+            .segmentation(Segmentation::BlankLines)
+            .align_comments(CommentColumn::Shared)
+            .comment_marker(CommentMarker::line("#"));
+
+        //input
+        let in_str = r##"cp a b # copy it
+mv ccc dd # move
+rm e // not a comment
+
+ls # list
+chmod 600 file # private"##;
+
+        //expected:
+        let out_str = r##"cp a   b  # copy it
+mv ccc dd # move
+rm e   // not a comment
+
+ls             # list
+chmod 600 file # private"##;
+
+        assert_eq(&format_text(in_str, &cfg).unwrap(), out_str);
+    }
+
+    #[test]
+    fn test_stream() {
+        let cfgs = [
+            Config::new().auto().align_comments(CommentColumn::Shared).segmentation(Segmentation::BlankLines),
+            Config::new().auto().align_comments(CommentColumn::Fixed(12)),
+        ];
+
+        //input
+        let in_str = "a = 1; // one\nbbb = 2; // two\n\ncccccc = 3; // three\nd = 4;\n";
+        for cfg in cfgs.iter() {
+            let mut out : Vec<u8> = Vec::new();
+            format_stream(std::io::Cursor::new(in_str.as_bytes()), &mut out, cfg).unwrap();
+            assert_eq(&String::from_utf8(out).unwrap(), &format_text(in_str, cfg).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_args() {
+        let mut cfg = Config::new();
+        let args : Vec<String> = ["--auto", "--comment-column", "12", "--comment_marker", "<!-- -->"].iter().map(|s|s.to_string()).collect();
+        cfg.parse_args(args.iter()).unwrap();
+
+        //input
+        let in_str = "a = 1; <!-- one -->\nbbb = 2; // two";
+
+        //expected:
+        let out_str = "a   = 1;    <!-- one -->\nbbb = 2; // two";
+
+        assert_eq(&format_text(in_str, &cfg).unwrap(), out_str);
+
+        let args : Vec<String> = ["--comment_marker", "a b c"].iter().map(|s|s.to_string()).collect();
+        assert!(Config::new().parse_args(args.iter()).is_err());
+    }
+}
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod mytests {
+    use crate::tests::mytests::assert_eq;
+    use crate::config::Config;
+    use crate::auto_config::AutoMode;
+    use crate::column_tools::Align;
+    use crate::column_tools::SeparatorConfig;
+    use crate::format_text;
+
+    #[test]
+    fn test_named() {
+        let cfg = Config::new()
+            .analyzer("sep")
+            .separators(vec![','])
+            .new_column_separators(vec![',', ' '])
+            .align(Align::Left)
+            .separator_config(",: :1".parse::<SeparatorConfig>().unwrap());
+
+        //input
+        let in_str = r##"
+a, bb, c
+dddd, e, ff"##;
+        
+        //expected:
+        let out_str = r##"
+a   , bb, c
+dddd, e , ff"##;
+
+        let result = format_text(in_str, &cfg).unwrap();
+
+        assert_eq(&result, out_str);
+    }
+
+    #[test]
+    fn test_auto() {
+        let cfg = Config::new().auto();
+
+        //input
+        let in_str = r##"MyClass = 12;
+int = 155;"##;
+        
+        //expected:
+        let out_str = r##"MyClass = 12;
+int     = 155;"##;
+
+        let result = format_text(in_str, &cfg).unwrap();
+        assert_eq(&result, out_str);
+
+        let result = format_text(in_str, &Config::new().auto_mode(AutoMode::SimpleVarAssignment)).unwrap();
+        assert_eq(&result, out_str);
+    }
+
+    #[test]
+    fn test_args() {
+        let args : Vec<String> = ["--analyzer", "sep", "--seps", ",", "--align", "right", "--sep_config", ",: :1"].iter().map(|s|s.to_string()).collect();
+        let mut cfg = Config::new();
+        cfg.parse_args(args.iter()).unwrap();
+
+        let result = format_text("a,bbb\ncc,d", &cfg).unwrap();
+        assert_eq(&result, " a, bbb\ncc,   d");
+
+        assert!(format_text("a", &Config::new().analyzer("unknown")).is_err());
+    }
+
+    #[test]
+    fn test_bad_args() {
+        let cases : [&[&str]; 7] = [
+            &["--fill_count", "abc"],
+            &["--seps"],
+            &["--column"],
+            &["--analyzer", "nosuch"],
+            &["--tab_width", "x"],
+            &["--fill", ""],
+            &["--decimal_sep", ",."],
+        ];
+        for case in cases.iter() {
+            let args : Vec<String> = case.iter().map(|s|s.to_string()).collect();
+            let err = Config::new().parse_args(args.iter()).unwrap_err().to_string();
+            assert!(err.starts_with(case[0]), "{}", err);
+        }
+    }
+}
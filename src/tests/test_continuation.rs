@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod mytests {
+    use crate::tests::mytests::assert_eq;
+    use crate::config::Config;
+    use crate::column_tools::TabMode;
+    use crate::analyzers::continuation::ContinuationColumn;
+    use crate::auto_config::AutoMode;
+    use crate::auto_config::auto_analyze_lines;
+    use crate::auto_config::cursor_block;
+    use crate::lines::LineRange;
+    use crate::format_text;
+
+    //input
+    const IN_STR : &str = r##"#define SWAP(a, b) \
+    do { \
+        typeof(a) tmp = (a); \
+        (a) = (b); (b) = tmp;   \
+    } while (0)"##;
+
+    #[test]
+    fn test_auto() {
+        let lines : Vec<&str> = IN_STR.lines().collect();
+        assert!(auto_analyze_lines(&lines) == AutoMode::Continuation);
+
+        //expected:
+        let out_str = r##"#define SWAP(a, b)            \
+    do {                      \
+        typeof(a) tmp = (a);  \
+        (a) = (b); (b) = tmp; \
+    } while (0)"##;
+
+        assert_eq(&format_text(IN_STR, &Config::new().auto()).unwrap(), out_str);
+    }
+
+    #[test]
+    fn test_stray_backslash() {
+        //a path ending in '\' doesn't make the block continued lines
+        let lines = vec!["see C:\\dir\\", "some words here and more", "x"];
+        assert!(auto_analyze_lines(&lines) == AutoMode::SimpleSpace);
+
+        //input
+        let in_str = r##"root = C:\data\
+level = 3
+x = 1"##;
+        let lines : Vec<&str> = in_str.lines().collect();
+        let (mode, range) = cursor_block(&lines, 1).unwrap();
+        assert!(mode == AutoMode::SimpleVarAssignment);
+        assert_eq!(range, LineRange{begin : 1, end : 3});
+
+        //neighbours ending in '\' are continued lines
+        let lines : Vec<&str> = IN_STR.lines().collect();
+        assert!(cursor_block(&lines, 2).unwrap().0 == AutoMode::Continuation);
+    }
+
+    #[test]
+    fn test_fixed_column() {
+        let cfg = Config::new().auto().continuation_column(ContinuationColumn::Fixed(32));
+
+        //expected:
+        let out_str = r##"#define SWAP(a, b)              \
+    do {                        \
+        typeof(a) tmp = (a);    \
+        (a) = (b); (b) = tmp;   \
+    } while (0)"##;
+
+        assert_eq(&format_text(IN_STR, &cfg).unwrap(), out_str);
+    }
+
+    #[test]
+    fn test_tab_stop() {
+        let cfg = Config::new().analyzer("continuation").tab_width(8).tab_mode(TabMode::Smart)
+            .continuation_column(ContinuationColumn::TabStop);
+
+        //input
+        let in_str = "#define F(x) \\\n\tdo_it(x); \\\n\tdone()";
+
+        //expected:
+        let out_str = "#define F(x)     \t\\\n\tdo_it(x);\t\\\n\tdone()";
+
+        assert_eq(&format_text(in_str, &cfg).unwrap(), out_str);
+    }
+
+    #[test]
+    fn test_args() {
+        let mut cfg = Config::new();
+        let args : Vec<String> = ["--analyzer", "continuation", "--continuation-column", "shared"].iter().map(|s|s.to_string()).collect();
+        cfg.parse_args(args.iter()).unwrap();
+
+        //input
+        let in_str = "ls -la \\\n  --color=auto \\\n  /tmp";
+
+        //expected:
+        let out_str = "ls -la         \\\n  --color=auto \\\n  /tmp";
+
+        assert_eq(&format_text(in_str, &cfg).unwrap(), out_str);
+
+        let args : Vec<String> = ["--continuation-column", "right"].iter().map(|s|s.to_string()).collect();
+        assert!(Config::new().parse_args(args.iter()).is_err());
+    }
+}
@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod mytests {
+    use crate::tests::mytests::assert_eq;
+    use crate::config::Config;
+    use crate::column_tools::Align;
+    use crate::column_tools::NumericAlign;
+    use crate::streaming::format_stream;
+    use crate::format_text;
+
+    #[test]
+    fn test_empty_and_quoted() {
+        let cfg = Config::new().analyzer("csv").align(Align::Left);
+
+        //input
+        let in_str = r##"a,,c
+"x, y",b,
+dd,"say ""hi""",e
+"multi
+line",f,g
+h,i,j"##;
+
+        //expected:
+        let out_str = r##"a     ,            ,c
+"x, y",b           ,
+dd    ,"say ""hi""",e
+"multi
+line",f,g
+h     ,i           ,j"##;
+
+        assert_eq(&format_text(in_str, &cfg).unwrap(), out_str);
+        //aligned output is aligned already
+        assert_eq(&format_text(out_str, &cfg).unwrap(), out_str);
+    }
+
+    #[test]
+    fn test_multi_line() {
+        let cfg = Config::new().analyzer("csv").align(Align::Left);
+
+        //input
+        let in_str = "a,b\nx,\"very long multi   \n  line \n\",f  \nab,c\n";
+
+        //expected: the opening part doesn't widen the column, whitespace inside the quotes is kept
+        let out_str = "a ,b\nx ,\"very long multi   \n  line \n\",f\nab,c";
+
+        assert_eq!(format_text(in_str, &cfg).unwrap(), out_str);
+
+        let mut out : Vec<u8> = Vec::new();
+        format_stream(std::io::Cursor::new(in_str.as_bytes()), &mut out, &cfg).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), out_str);
+    }
+
+    #[test]
+    fn test_header() {
+        let cfg = Config::new().analyzer("tsv").align(Align::Left).numeric_align(NumericAlign::Right).csv_header(true);
+
+        //input
+        let in_str = "name\tcount\nfoo\t7\nbarbaz\t1200";
+
+        //expected:
+        let out_str = "name  \tcount\nfoo   \t    7\nbarbaz\t 1200";
+
+        assert_eq(&format_text(in_str, &cfg).unwrap(), out_str);
+    }
+
+    #[test]
+    fn test_dialect() {
+        let args : Vec<String> = ["--analyzer", "csv", "--align", "left", "--csv_delimiter", ";", "--csv_quote", "'", "--csv_escape", "\\"]
+            .iter().map(|s|s.to_string()).collect();
+        let mut cfg = Config::new();
+        cfg.parse_args(args.iter()).unwrap();
+
+        //input
+        let in_str = r##"'it\'s; here';1
+b;22"##;
+
+        //expected:
+        let out_str = r##"'it\'s; here';1
+b            ;22"##;
+
+        assert_eq(&format_text(in_str, &cfg).unwrap(), out_str);
+
+        let args : Vec<String> = vec!["--csv_delimiter".to_string(), ";;".to_string()];
+        let err = Config::new().parse_args(args.iter()).unwrap_err();
+        assert_eq!(err.to_string(), "--csv_delimiter ;;: expected single char or tab, found ';;'");
+    }
+}
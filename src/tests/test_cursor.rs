@@ -0,0 +1,50 @@
+#[cfg(test)]
+mod mytests {
+    use crate::tests::mytests::assert_eq;
+    use crate::auto_config::AutoMode;
+    use crate::auto_config::cursor_block;
+    use crate::lines::LineRange;
+    use crate::in_place::format_cursor_block;
+    use crate::config::Config;
+
+    #[test]
+    fn test_block() {
+        let lines = vec!["#include <x>", "", "int a = 1;", "float bbb = 2;", "x(1,2);"];
+        let (mode, range) = cursor_block(&lines, 4).unwrap();
+        assert_eq!(mode, AutoMode::SimpleAssignment);
+        assert_eq!(range, LineRange{begin : 3, end : 4});
+
+        assert!(cursor_block(&lines, 2).is_none());
+        assert!(cursor_block(&lines, 6).is_none());
+        assert!(cursor_block(&lines, 0).is_none());
+    }
+
+    #[test]
+    fn test_ignored() {
+        //input
+        let in_str = r##"x
+{1, 2},
+// c
+{333, 4},
+// d
+
+{5, 6},
+"##;
+        
+        //expected:
+        let out_str = r##"x
+{1  , 2},
+// c
+{333, 4},
+// d
+
+{5, 6},
+"##;
+
+        let (res, range) = format_cursor_block(in_str, 4, &Config::new()).unwrap();
+        assert_eq!(range, LineRange{begin : 2, end : 4});
+        assert_eq(&res, out_str);
+
+        assert!(format_cursor_block(in_str, 6, &Config::new()).is_err());
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod mytests {
+    use crate::analyzers::AnalyzeErrKind;
+    use crate::analyzers::LineAnalyzer;
+    use crate::analyzers::bit_field::Analyzer as BitFieldAnalyzer;
+    use crate::analyzers::xml_attr::Analyzer as XmlAttrAnalyzer;
+    use crate::column_tools::ColumnStyle;
+    use crate::column_tools::ParseErrKind;
+    use crate::column_tools::SeparatorConfig;
+    use crate::config::Config;
+    use crate::format_text_with_diagnostics;
+    use crate::LineIssue;
+
+    #[test]
+    fn test_analyze_err() {
+        let bf = BitFieldAnalyzer{};
+        let e = bf.can_accept("unsigned a : x;").unwrap_err();
+        assert_eq!(e.kind, AnalyzeErrKind::InvalidNumber);
+        assert_eq!(e.offset, Some(13));
+        assert!(e.source.is_some());
+
+        let e = bf.can_accept("unsigned a : 3").unwrap_err();
+        assert_eq!(e.kind, AnalyzeErrKind::Missing);
+        assert_eq!(e.expected.as_deref(), Some("';'"));
+        assert_eq!(e.offset, Some(14));
+
+        //failed backward searches point where they started
+        let e = bf.can_accept("   : 3;").unwrap_err();
+        assert_eq!(e.kind, AnalyzeErrKind::Missing);
+        assert_eq!(e.expected.as_deref(), Some("non-whitespace"));
+        assert_eq!(e.offset, Some(3));
+
+        let e = bf.can_accept("a : 3;").unwrap_err();
+        assert_eq!(e.expected.as_deref(), Some("whitespace"));
+        assert_eq!(e.offset, Some(1));
+
+        let xml = XmlAttrAnalyzer{};
+        let e = xml.can_accept("  <a b=\"1\"").unwrap_err();
+        assert_eq!(e.kind, AnalyzeErrKind::Unexpected);
+        assert_eq!(e.offset, Some(9));
+        assert_eq!(e.to_string(), "expected '>' at byte 9");
+    }
+
+    #[test]
+    fn test_parse_err() {
+        let e = ",::x".parse::<SeparatorConfig>().err().unwrap();
+        assert_eq!(e.kind, ParseErrKind::Missing);
+        assert_eq!(e.offset, Some(2));
+
+        let e = ",: :x".parse::<SeparatorConfig>().err().unwrap();
+        assert_eq!(e.kind, ParseErrKind::InvalidNumber);
+        assert_eq!(e.offset, Some(4));
+        assert!(e.source.is_some());
+
+        let e = "1:align=left,wdth=3".parse::<ColumnStyle>().err().unwrap();
+        assert_eq!(e.kind, ParseErrKind::Invalid);
+        assert_eq!(e.found.as_deref(), Some("wdth"));
+        assert_eq!(e.offset, Some(13));
+
+        let args : Vec<String> = vec!["--sep_config".to_string(), ",: :1:middle".to_string()];
+        let err = Config::new().parse_args(args.iter()).unwrap_err();
+        assert_eq!(err.to_string(), "--sep_config ,: :1:middle: expected left, right or center, found 'middle' at 6");
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let cfg = Config::new().analyzer("bit_field");
+
+        //input
+        let in_str = r##"int a : 3;
+unsigned bbb : x;
+
+// not a field
+char c : 12;"##;
+
+        let (_, diags) = format_text_with_diagnostics(in_str, &cfg).unwrap();
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].line, 2);
+        assert_eq!(diags[0].offset(), Some(15));
+        assert_eq!(diags[1].line, 4);
+        assert!(matches!(&diags[1].issue, LineIssue::Rejected(e) if e.kind == AnalyzeErrKind::Missing));
+        assert_eq!(diags[1].offset(), Some(14));
+
+        let cfg = Config::new().analyzer("sep").separators(vec![',']).non_matched_as_is(true);
+        let (_, diags) = format_text_with_diagnostics("a,b,c\na,b\nd,e,f", &cfg).unwrap();
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].line, 2);
+        assert!(matches!(diags[0].issue, LineIssue::ColumnCount{found : 2, expected : 3}));
+    }
+}
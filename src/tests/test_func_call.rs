@@ -14,11 +14,11 @@ some_func(int, float);
 Class::Method(char a, SomeStruct{23, 45.5, 22});//other comment
 yet_another_func(int, int);//some comment"##;
         
-        //expected: (currently there are spaces at the end of each line except the longest one)
+        //expected:
         let out_str = r##"
-some_func       (int   , float                   );               
+some_func       (int   , float                   );
 Class::Method   (char a, SomeStruct{23, 45.5, 22});//other comment
-yet_another_func(int   , int                     );//some comment "##;
+yet_another_func(int   , int                     );//some comment"##;
 
         let result = run_analyzer(in_str, cfg.analyzer.as_mut(), cfg.formatter, cfg.printer);
 
@@ -17,12 +17,12 @@ ComplexReturn Class2::AnotherMethod();
 int yet_another_func(int, int, int, int, int);
     "##;
         
-        //expected: (currently there are spaces at the end of each line except the longest one)
+        //expected:
         let out_str = r##"
-int           some_func            (int, float);             
-char          Class::Method        (char a, SomeStruct B);   
+int           some_func            (int, float);
+char          Class::Method        (char a, SomeStruct B);
 
-ComplexReturn Class2::AnotherMethod();                       
+ComplexReturn Class2::AnotherMethod();
 int           yet_another_func     (int, int, int, int, int);
     "##;
 
@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod mytests {
+    use crate::tests::mytests::assert_eq;
+    use crate::lines::LineRange;
+    use crate::in_place::format_line_ranges;
+    use crate::in_place::write_atomic;
+    use crate::config::Config;
+    use crate::column_tools::Align;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("40:58".parse::<LineRange>().unwrap(), LineRange{begin : 40, end : 58});
+        assert!("0:3".parse::<LineRange>().is_err());
+        assert!("5:3".parse::<LineRange>().is_err());
+        assert!("5".parse::<LineRange>().is_err());
+    }
+
+    #[test]
+    fn test_ranges() {
+        let cfg = Config::new().separators(vec![',']).align(Align::Left);
+
+        //input
+        let in_str = "x,yyyy\r\na,b\r\nccc,d\n// keep  \ne,f\ngg,h\nzzzz,w\n";
+        
+        //expected:
+        let out_str = "x,yyyy\r\na  ,b\r\nccc,d\n// keep  \ne ,f\ngg,h\nzzzz,w\n";
+
+        let ranges = vec![LineRange{begin : 5, end : 6}, LineRange{begin : 2, end : 3}];
+        let (res, _) = format_line_ranges(in_str, &ranges, &cfg).unwrap();
+        assert_eq(&res, out_str);
+
+        //past the end is cut, overlapping is merged
+        let ranges = vec![LineRange{begin : 5, end : 100}, LineRange{begin : 6, end : 7}];
+        let (res, _) = format_line_ranges(in_str, &ranges, &cfg).unwrap();
+        assert_eq(&res, "x,yyyy\r\na,b\r\nccc,d\n// keep  \ne   ,f\ngg  ,h\nzzzz,w\n");
+
+        assert!(format_line_ranges(in_str, &[LineRange{begin : 8, end : 9}], &cfg).is_err());
+    }
+
+    #[test]
+    fn test_trailing_blank_lines() {
+        let cfg = Config::new().separators(vec![',']).align(Align::Left);
+
+        //input
+        let in_str = "a,b\nccc,d\n\nz\n";
+
+        //expected:
+        let out_str = "a  ,b\nccc,d\n\nz\n";
+
+        let (res, _) = format_line_ranges(in_str, &[LineRange{begin : 1, end : 3}], &cfg).unwrap();
+        assert_eq(&res, out_str);
+
+        //blank lines only
+        let (res, _) = format_line_ranges(in_str, &[LineRange{begin : 3, end : 3}], &cfg).unwrap();
+        assert_eq(&res, in_str);
+
+        //trailing blank lines of the input
+        let (res, _) = format_line_ranges("a,b\nccc,d\n  \n\n", &[LineRange{begin : 1, end : 4}], &cfg).unwrap();
+        assert_eq(&res, "a  ,b\nccc,d\n  \n\n");
+    }
+
+    #[test]
+    fn test_write_atomic() {
+        let root = std::env::temp_dir().join(format!("format-columns-in-place-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        let path = root.join("src.txt");
+        std::fs::write(&path, "old").unwrap();
+
+        write_atomic(&path, "new", Some(".bak")).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(std::fs::read_to_string(root.join("src.txt.bak")).unwrap(), "old");
+        //no temporary files left
+        assert_eq!(std::fs::read_dir(&root).unwrap().count(), 2);
+
+        assert!(write_atomic(&root.join("missing").join("src.txt"), "new", None).is_err());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomic_symlink() {
+        let root = std::env::temp_dir().join(format!("format-columns-symlink-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        let target = root.join("target.txt");
+        let link = root.join("link.txt");
+        std::fs::write(&target, "old").unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        write_atomic(&link, "new", None).unwrap();
+        assert!(std::fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "new");
+        assert_eq!(std::fs::read_dir(&root).unwrap().count(), 2);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod mytests {
+    use crate::tests::mytests::assert_eq;
+    use crate::config::Config;
+    use crate::column_tools::IndentPolicy;
+    use crate::format_text;
+
+    //input
+    const IN_STR : &str = r##"    {"SomeApi::Func1", &SomeApi::Func1},
+   {"SomeOtherApi::CoolMethod", &SomeOtherApi::CoolMyMethod},
+  {"JustApi::Boring", &JustApi::Boring},
+ {"OneMore::WhoNeedsThis", &OneMore::WhoNeedsThis},"##;
+
+    #[test]
+    fn test_keep() {
+        let cfg = Config::new().auto().indent(IndentPolicy::Keep);
+
+        //expected:
+        let out_str = r##"    {"SomeApi::Func1"          , &SomeApi::Func1            },
+   {"SomeOtherApi::CoolMethod", &SomeOtherApi::CoolMyMethod},
+  {"JustApi::Boring"         , &JustApi::Boring           },
+ {"OneMore::WhoNeedsThis"   , &OneMore::WhoNeedsThis     },"##;
+
+        assert_eq(&format_text(IN_STR, &cfg).unwrap(), out_str);
+    }
+
+    #[test]
+    fn test_first_min_fixed() {
+        //expected:
+        let out_str = r##"    {"SomeApi::Func1"          , &SomeApi::Func1            },
+    {"SomeOtherApi::CoolMethod", &SomeOtherApi::CoolMyMethod},
+    {"JustApi::Boring"         , &JustApi::Boring           },
+    {"OneMore::WhoNeedsThis"   , &OneMore::WhoNeedsThis     },"##;
+
+        assert_eq(&format_text(IN_STR, &Config::new().auto().indent(IndentPolicy::First)).unwrap(), out_str);
+        assert_eq(&format_text(IN_STR, &Config::new().auto()).unwrap(), out_str);
+        assert_eq(&format_text(IN_STR, &Config::new().auto().indent(IndentPolicy::Fixed(4))).unwrap(), out_str);
+
+        let min = format_text(IN_STR, &Config::new().auto().indent(IndentPolicy::Min)).unwrap();
+        assert_eq(&min, &out_str.replace("\n    ", "\n ")[3..]);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("keep".parse::<IndentPolicy>().unwrap(), IndentPolicy::Keep);
+        assert_eq!("8".parse::<IndentPolicy>().unwrap(), IndentPolicy::Fixed(8));
+        assert!("deep".parse::<IndentPolicy>().is_err());
+    }
+}
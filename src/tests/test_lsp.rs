@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod mytests {
+    use serde_json::json;
+    use serde_json::Value;
+    use crate::config::Config;
+    use crate::column_tools::Align;
+    use crate::lsp::Server;
+    use crate::lsp::read_message;
+    use crate::lsp::write_message;
+    use crate::lsp::text_edits;
+
+    //runs the server over a scripted session and returns its responses
+    fn run_session(msgs :&[Value]) -> Vec<Value> {
+        run_server(Server::new(Config::new().auto()), msgs)
+    }
+
+    fn run_server(mut server :Server, msgs :&[Value]) -> Vec<Value> {
+        let mut input : Vec<u8> = Vec::new();
+        msgs.iter().for_each(|m|write_message(&mut input, m).unwrap());
+
+        let mut output : Vec<u8> = Vec::new();
+        server.run(&input[..], &mut output).unwrap();
+
+        let mut resp = Vec::new();
+        let mut out = &output[..];
+        while let Some(m) = read_message(&mut out).unwrap() {
+            resp.push(m);
+        }
+        resp
+    }
+
+    #[test]
+    fn test_edits() {
+        let edits = text_edits("a=1\nä =22\r\nc\n", "a=1\nä=22\r\nc\n");
+        assert_eq!(edits, vec![json!({"range" : {"start" : {"line" : 1, "character" : 0}, "end" : {"line" : 1, "character" : 5}}, "newText" : "ä=22"})]);
+    }
+
+    #[test]
+    fn test_edits_line_count() {
+        //only the wrapped line is replaced
+        let edits = text_edits("a\nb, long\nc\n", "a\nb,\n  long\nc\n");
+        assert_eq!(edits, vec![json!({"range" : {"start" : {"line" : 1, "character" : 0}, "end" : {"line" : 2, "character" : 0}}, "newText" : "b,\n  long\n"})]);
+
+        let edits = text_edits("a\nb, long", "a\nb,\n  long");
+        assert_eq!(edits, vec![json!({"range" : {"start" : {"line" : 1, "character" : 0}, "end" : {"line" : 1, "character" : 7}}, "newText" : "b,\n  long"})]);
+    }
+
+    #[test]
+    fn test_config_for() {
+        let uri = "file:///src/my%20dir/a.csv";
+        let formatting = |id :i64, uri :&str|json!({"jsonrpc" : "2.0", "id" : id, "method" : "textDocument/formatting", "params" : {"textDocument" : {"uri" : uri}}});
+        let server = Server::new(Config::new().separators(vec![',']).align(Align::Left))
+            .config_for(|path|{
+                assert_eq!(path, std::path::Path::new("/src/my dir/a.csv"));
+                Ok(Config::new().separators(vec![',']).align(Align::Right))
+            });
+        let resp = run_server(server, &[
+            json!({"jsonrpc" : "2.0", "method" : "textDocument/didOpen", "params" : {"textDocument" : {"uri" : uri, "text" : "a,b\nccc,d\n\n"}}}),
+            json!({"jsonrpc" : "2.0", "method" : "textDocument/didOpen", "params" : {"textDocument" : {"uri" : "untitled:1", "text" : "a,b\nccc,d\n\n"}}}),
+            formatting(1, uri),
+            formatting(2, "untitled:1"),
+        ]);
+
+        //the trailing blank line stays
+        assert_eq!(resp[0]["result"], json!([
+            {"range" : {"start" : {"line" : 0, "character" : 0}, "end" : {"line" : 0, "character" : 3}}, "newText" : "  a,b"}
+        ]));
+        assert_eq!(resp[1]["result"], json!([
+            {"range" : {"start" : {"line" : 0, "character" : 0}, "end" : {"line" : 0, "character" : 3}}, "newText" : "a  ,b"}
+        ]));
+    }
+
+    #[test]
+    fn test_session() {
+        let uri = "file:///src/a.c";
+        let text = "int a = 1;\nfloat bbb = 2;\n\nx(1, 2);\n";
+        let resp = run_session(&[
+            json!({"jsonrpc" : "2.0", "id" : 1, "method" : "initialize", "params" : {}}),
+            json!({"jsonrpc" : "2.0", "method" : "initialized", "params" : {}}),
+            json!({"jsonrpc" : "2.0", "method" : "textDocument/didOpen", "params" : {"textDocument" : {"uri" : uri, "text" : text}}}),
+            json!({"jsonrpc" : "2.0", "id" : 2, "method" : "textDocument/rangeFormatting", "params" : {
+                "textDocument" : {"uri" : uri},
+                "range" : {"start" : {"line" : 0, "character" : 3}, "end" : {"line" : 2, "character" : 0}},
+                "options" : {"tabSize" : 4, "insertSpaces" : true}}}),
+            json!({"jsonrpc" : "2.0", "method" : "textDocument/didChange", "params" : {
+                "textDocument" : {"uri" : uri, "version" : 2},
+                "contentChanges" : [{"text" : "int   a   = 1;\nfloat bbb = 2;\n"}]}}),
+            json!({"jsonrpc" : "2.0", "id" : 3, "method" : "textDocument/formatting", "params" : {"textDocument" : {"uri" : uri}, "options" : {}}}),
+            json!({"jsonrpc" : "2.0", "id" : 4, "method" : "textDocument/hover", "params" : {}}),
+            json!({"jsonrpc" : "2.0", "id" : 5, "method" : "shutdown"}),
+            json!({"jsonrpc" : "2.0", "method" : "exit"}),
+        ]);
+
+        assert_eq!(resp.len(), 5);
+        assert_eq!(resp[0]["result"]["capabilities"]["documentRangeFormattingProvider"], json!(true));
+        assert_eq!(resp[1]["id"], json!(2));
+        assert_eq!(resp[1]["result"], json!([
+            {"range" : {"start" : {"line" : 0, "character" : 0}, "end" : {"line" : 0, "character" : 10}}, "newText" : "int   a   = 1;"}
+        ]));
+        //already aligned
+        assert_eq!(resp[2]["result"], json!([]));
+        assert_eq!(resp[3]["error"]["code"], json!(-32601));
+        assert_eq!(resp[4]["result"], Value::Null);
+    }
+}
@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod mytests {
+    use crate::tests::mytests::assert_eq;
+    use crate::auto_config::*;
+    use crate::config::Config;
+    use crate::format_text;
+
+    #[test]
+    fn test_match() {
+        let cfg = Config::new().auto();
+
+        //input
+        let in_str = r##"        match v {
+            Some(x) if x > 0 => x,
+            // negative
+            Some(Point { x, y }) => {
+                x + y
+            },
+            None => 0,
+            _ =>
+                panic!("a => b"),
+        }"##;
+
+        //expected:
+        let out_str = r##"        match v {
+            Some(x) if x > 0     => x,
+            // negative
+            Some(Point { x, y }) => {
+                x + y
+            },
+            None                 => 0,
+            _                    =>
+                panic!("a => b"),
+        }"##;
+
+        assert_eq(&format_text(in_str, &cfg).unwrap(), out_str);
+    }
+
+    #[test]
+    fn test_hash() {
+        let cfg = Config::new().auto_mode(AutoMode::MatchArm);
+
+        //input
+        let in_str = r##"  'one' => 1,
+  "a=>b" => [1 => 2],
+  :three => 3,"##;
+
+        //expected:
+        let out_str = r##"  'one'  => 1,
+  "a=>b" => [1 => 2],
+  :three => 3,"##;
+
+        assert_eq(&format_text(in_str, &cfg).unwrap(), out_str);
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(auto_analyze("    Some(x) if x > 0 => x,"), AutoMode::MatchArm);
+        assert_eq!(auto_analyze("    case Foo(a) => a"), AutoMode::MatchArm);
+        assert_eq!(auto_analyze("    'a'..='z' if c != x => 1,"), AutoMode::MatchArm);
+        assert_eq!(auto_analyze("    x = y => z;"), AutoMode::SimpleVarAssignment);
+    }
+}
@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod mytests {
+    use crate::tests::mytests::assert_eq;
+    use crate::config::Config;
+    use crate::auto_config::AutoMode;
+    use crate::column_tools::split_number;
+    use crate::column_tools::NumericAlign;
+    use crate::format_text;
+
+    #[test]
+    fn test_split() {
+        assert_eq!(split_number("12.5f", '.'), Some((2, 3)));
+        assert_eq!(split_number("-3", '.'), Some((2, 0)));
+        assert_eq!(split_number("1e-5", '.'), Some((1, 3)));
+        assert_eq!(split_number("0xFF", '.'), Some((4, 0)));
+        assert_eq!(split_number(".5", '.'), Some((0, 2)));
+        assert_eq!(split_number("1,25", ','), Some((1, 3)));
+        assert_eq!(split_number("abc", '.'), None);
+        assert_eq!(split_number("1.2.3", '.'), None);
+        assert_eq!(split_number("-", '.'), None);
+    }
+
+    #[test]
+    fn test_decimal() {
+        let cfg = Config::new().auto_mode(AutoMode::SimpleComma).numeric_align(NumericAlign::Decimal);
+
+        //input
+        let in_str = r##"
+pi, 3.14159, 1
+e, 2.71f, -12
+big, 1200, 0x1F
+tiny, 1e-5, 7"##;
+        
+        //expected:
+        let out_str = r##"
+pi  ,    3.14159,    1
+e   ,    2.71f  ,  -12
+big , 1200      , 0x1F
+tiny,    1e-5   ,    7"##;
+
+        let result = format_text(in_str, &cfg).unwrap();
+
+        assert_eq(&result, out_str);
+    }
+
+    #[test]
+    fn test_right() {
+        let mut cfg = Config::new().auto_mode(AutoMode::SimpleComma).numeric_align(NumericAlign::Right);
+
+        //input
+        let in_str = r##"
+a, 1.5, x
+bb, 10, yy"##;
+        
+        //expected:
+        let out_str = r##"
+a , 1.5, x
+bb,  10, yy"##;
+
+        let result = format_text(in_str, &cfg).unwrap();
+        assert_eq(&result, out_str);
+
+        let args : Vec<String> = ["--numeric", "decimal", "--decimal_sep", ","].iter().map(|s|s.to_string()).collect();
+        cfg = Config::new().auto_mode(AutoMode::SimpleSpace);
+        cfg.parse_args(args.iter()).unwrap();
+        let result = format_text("a 1,5\nbb 100", &cfg).unwrap();
+        assert_eq(&result, "a    1,5\nbb 100");
+    }
+}
@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod mytests {
+    use crate::tests::mytests::assert_eq;
+    use crate::tests::mytests::run_analyzer as run_analyzer;
+    use crate::auto_config::*;
+    use crate::config::Config;
+    use crate::format_text;
+
+    #[test]
+    fn test_markdown() {
+        let mut cfg = do_auto_config(AutoMode::PipeTable);
+
+        //input
+        let in_str = r##"
+  | Name | Value | Notes |
+  |:--|--:|:-:|
+| a | 1 | `x|y` and \| |
+|longer name|12345|z
+"##;
+
+        //expected:
+        let out_str = r##"
+  | Name        | Value |    Notes     |
+  | :---------- | ----: | :----------: |
+  | a           |     1 | `x|y` and \| |
+  | longer name | 12345 |      z       |
+"##;
+
+        let result = run_analyzer(in_str, cfg.analyzer.as_mut(), cfg.formatter, cfg.printer);
+
+        assert_eq(&result, out_str);
+    }
+
+    #[test]
+    fn test_org() {
+        let cfg = Config::new().auto();
+
+        //input
+        let in_str = r##"|---+---|
+| a | bbbb |
+|---+---|
+| ccc | ``d | e`` |"##;
+
+        //expected:
+        let out_str = r##"|-----+-----------|
+| a   | bbbb      |
+|-----+-----------|
+| ccc | ``d | e`` |"##;
+
+        assert_eq(&format_text(in_str, &cfg).unwrap(), out_str);
+    }
+
+    #[test]
+    fn test_dash_cells() {
+        let cfg = Config::new().auto();
+
+        //input
+        let in_str = r##"| a | b |
+| - | - |
+| x | yy |
+|---|:-|
+| -- | -:- |"##;
+
+        //expected: only the delimiter row is a rule
+        let out_str = r##"| a   | b   |
+| -   | -   |
+| x   | yy  |
+| --- | :-- |
+| --  | -:- |"##;
+
+        assert_eq(&format_text(in_str, &cfg).unwrap(), out_str);
+    }
+
+    #[test]
+    fn test_gherkin() {
+        let cfg = Config::new().auto();
+
+        //input
+        let in_str = r##"    Examples:
+      | start | eat | left |
+      |    12 |   5 |    7 |
+      | 120 | 50 | 70 |"##;
+
+        //expected:
+        let out_str = r##"    Examples:
+      | start | eat | left |
+      | 12    | 5   | 7    |
+      | 120   | 50  | 70   |"##;
+
+        assert_eq(&format_text(in_str, &cfg).unwrap(), out_str);
+
+        assert_eq!(auto_analyze("  | a | b |"), AutoMode::PipeTable);
+        assert_eq!(auto_analyze("  a | b |"), AutoMode::SimpleSpace);
+    }
+}
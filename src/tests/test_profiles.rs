@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod mytests {
+    use crate::tests::mytests::assert_eq;
+    use crate::config::Config;
+    use crate::profiles::*;
+    use crate::segments::Segmentation;
+    use crate::format_text;
+
+    const PROFILES : &str = r##"
+[profiles.default]
+auto = true
+
+[profiles.csv]
+analyzer = "sep"
+seps = ","
+align = "left"
+sep_config = [",: :1"]
+column = ["-1:align=right"]
+prestart = false
+"##;
+
+    #[test]
+    fn test_args() {
+        let args = load_profile_str(PROFILES, Some("csv")).unwrap();
+        assert_eq!(args, vec!["--align", "left", "--analyzer", "sep", "--column", "-1:align=right", "--sep_config", ",: :1", "--seps", ","]);
+
+        let args = load_profile_str(PROFILES, None).unwrap();
+        assert_eq!(args, vec!["--auto"]);
+
+        assert!(load_profile_str(PROFILES, Some("missing")).is_err());
+        assert!(load_profile_str("[profiles]\nx = 1", Some("x")).is_err());
+        assert!(load_profile_str("", None).unwrap().is_empty());
+
+        //typos and options that only the command line has
+        let err = load_profile_str("[profiles.x]\nalign = \"left\"\nsepz = \",\"", Some("x")).unwrap_err();
+        assert_eq!(err.to_string(), "unknown option --sepz");
+        assert!(load_profile_str("[profiles.x]\nin-place = true", Some("x")).is_err());
+        assert!(load_profile_str("[profiles.x]\nalign = \"up\"", Some("x")).is_err());
+    }
+
+    #[test]
+    fn test_override() {
+        let mut cfg = Config::new();
+        cfg.parse_args(load_profile_str(PROFILES, Some("csv")).unwrap().iter()).unwrap();
+
+        let result = format_text("a,bbb,1\ncc,d,22", &cfg).unwrap();
+        assert_eq(&result, "a , bbb,  1\ncc, d  , 22");
+
+        //command line comes after the profile
+        let cli : Vec<String> = ["--align", "right"].iter().map(|s|s.to_string()).collect();
+        cfg.parse_args(cli.iter()).unwrap();
+        let result = format_text("a,bbb,1\ncc,d,22", &cfg).unwrap();
+        assert_eq(&result, " a, bbb,  1\ncc,   d, 22");
+    }
+
+    #[test]
+    fn test_override_lists() {
+        let mut cfg = Config::new();
+        cfg.parse_args(load_profile_str(PROFILES, Some("csv")).unwrap().iter()).unwrap();
+
+        //the same separator and column configured again on the command line replace the profile ones
+        let cli : Vec<String> = ["--sep_config", ",:.:2", "--column", "-1:align=left"].iter().map(|s|s.to_string()).collect();
+        cfg.parse_args(cli.iter()).unwrap();
+        let result = format_text("a,bbb,1\ncc,d,22", &cfg).unwrap();
+        assert_eq(&result, "a ,..bbb,..1\ncc,..d  ,..22");
+    }
+
+    #[test]
+    fn test_merge_column_styles() {
+        let mut cfg = Config::new();
+        cfg.parse_args(load_profile_str(PROFILES, Some("csv")).unwrap().iter()).unwrap();
+
+        //the profile aligns the column, the command line adds a fill char to it
+        let cli : Vec<String> = ["--column", "-1:fill=."].iter().map(|s|s.to_string()).collect();
+        cfg.parse_args(cli.iter()).unwrap();
+        let result = format_text("a,bbb,1\ncc,d,22", &cfg).unwrap();
+        assert_eq(&result, "a , bbb, .1\ncc, d  , 22");
+    }
+
+    #[test]
+    fn test_override_segmentation() {
+        let mut cfg = Config::new();
+        let profile : Vec<String> = ["--segment", "regex:^#", "--segment", "blank"].iter().map(|s|s.to_string()).collect();
+        cfg.parse_args(profile.iter()).unwrap();
+        let cli : Vec<String> = ["--segment", "regex:^--"].iter().map(|s|s.to_string()).collect();
+        cfg.parse_args(cli.iter()).unwrap();
+        assert_eq!(cfg.segmentations().len(), 2);
+        assert!(matches!(&cfg.segmentations()[1], Segmentation::Regex(re) if re.as_str() == "^--"));
+    }
+
+    #[test]
+    fn test_find() {
+        let root = std::env::temp_dir().join(format!("format-columns-test-{}", std::process::id()));
+        let nested = root.join("a").join("b");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert!(find_config_file(&nested).is_none() || !find_config_file(&nested).unwrap().starts_with(&root));
+
+        std::fs::write(root.join(CONFIG_FILE_NAME), PROFILES).unwrap();
+        assert_eq!(find_config_file(&nested), Some(root.join(CONFIG_FILE_NAME)));
+        assert_eq!(load_profile(&root.join(CONFIG_FILE_NAME), None).unwrap(), vec!["--auto"]);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
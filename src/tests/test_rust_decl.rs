@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod mytests {
+    use crate::tests::mytests::run_analyzer;
+    use crate::tests::mytests::assert_eq;
+    use crate::auto_config::*;
+
+    #[test]
+    fn test_fields() {
+        let mut cfg = do_auto_config(AutoMode::RustDecl);
+
+        //input
+        let in_str = r##"
+    pub name: String,
+    pub(crate) map: HashMap<String, Vec<(u8, u8)>>, // lookup
+    id: u32,
+    cb: Box<dyn Fn(u8) -> u8>
+    "##;
+
+        //expected:
+        let out_str = r##"
+    pub        name: String,
+    pub(crate) map : HashMap<String, Vec<(u8, u8)>>, // lookup
+               id  : u32,
+               cb  : Box<dyn Fn(u8) -> u8>
+    "##;
+
+        let result = run_analyzer(in_str, cfg.analyzer.as_mut(), cfg.formatter, cfg.printer);
+
+        assert_eq(&result, out_str);
+    }
+
+    #[test]
+    fn test_bindings() {
+        let mut cfg = do_auto_config(AutoMode::RustDecl);
+
+        //input
+        let in_str = r##"
+const MAX: usize = 10;
+pub static mut COUNTER: AtomicUsize = AtomicUsize::new(0); // ticks
+let mut total: std::collections::HashMap<u8, u8> = HashMap::new();
+let same = a == b;
+"##;
+
+        //expected:
+        let out_str = r##"
+const          MAX    : usize                             = 10;
+pub static mut COUNTER: AtomicUsize                       = AtomicUsize::new(0); // ticks
+let mut        total  : std::collections::HashMap<u8, u8> = HashMap::new();
+let            same                                       = a == b;
+"##;
+
+        let result = run_analyzer(in_str, cfg.analyzer.as_mut(), cfg.formatter, cfg.printer);
+
+        assert_eq(&result, out_str);
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(auto_analyze("    pub name: String,"), AutoMode::RustDecl);
+        assert_eq!(auto_analyze("let x: u32 = 5;"), AutoMode::RustDecl);
+        assert_eq!(auto_analyze("static ref RE: Regex = Regex::new(\"a=b;\");"), AutoMode::RustDecl);
+        assert_ne!(auto_analyze("std::string s = x;"), AutoMode::RustDecl);
+        assert_ne!(auto_analyze("x = y;"), AutoMode::RustDecl);
+    }
+
+    #[test]
+    fn test_doc_comments() {
+        //input
+        let in_str = r##"    /// the x
+    pub x: f64,
+    /// longer name
+    pub name_long: String,
+    // plain
+    id: u32,"##;
+
+        let lines : Vec<&str> = in_str.lines().collect();
+        assert_eq!(auto_analyze_lines(&lines), AutoMode::RustDecl);
+        let (mode, range) = cursor_block(&lines, 2).unwrap();
+        assert_eq!(mode, AutoMode::RustDecl);
+        assert_eq!((range.begin, range.end), (2, 6));
+
+        //expected:
+        let out_str = r##"    /// the x
+    pub x        : f64,
+    /// longer name
+    pub name_long: String,
+    // plain
+        id       : u32,"##;
+
+        assert_eq(&crate::format_text(in_str, &crate::Config::new().auto()).unwrap(), out_str);
+    }
+}
@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod mytests {
+    use crate::tests::mytests::assert_eq;
+    use crate::config::Config;
+    use crate::auto_config::AutoMode;
+    use crate::segments::Segmentation;
+    use crate::format_text;
+
+    #[test]
+    fn test_blank() {
+        let cfg = Config::new().auto_mode(AutoMode::SimpleVarAssignment).segmentation(Segmentation::BlankLines);
+
+        //input
+        let in_str = r##"
+a = 1;
+bb = 2;
+
+very_long_name = 3;
+c = 4;"##;
+        
+        //expected:
+        let out_str = r##"
+a  = 1;
+bb = 2;
+
+very_long_name = 3;
+c              = 4;"##;
+
+        let result = format_text(in_str, &cfg).unwrap();
+        assert_eq(&result, out_str);
+    }
+
+    #[test]
+    fn test_indent() {
+        let cfg = Config::new().auto_mode(AutoMode::SimpleAssignment).segmentation(Segmentation::Indent);
+
+        //input
+        let in_str = r##"int a = 1;
+long bb = 2;
+    int very_long_name = 3;
+    char c = 4;
+int d = 5;"##;
+        
+        //expected:
+        let out_str = r##"int  a  = 1;
+long bb = 2;
+    int  very_long_name = 3;
+    char c              = 4;
+int d = 5;"##;
+
+        let result = format_text(in_str, &cfg).unwrap();
+        assert_eq(&result, out_str);
+    }
+
+    #[test]
+    fn test_regex_and_analyzer() {
+        let cfg = Config::new().auto_mode(AutoMode::SimpleVarAssignment).segmentation("regex:^//-".parse::<Segmentation>().unwrap());
+
+        //input
+        let in_str = r##"a = 1;
+bb = 2;
+//-----
+very_long_name = 3;
+c = 4;"##;
+        
+        //expected:
+        let out_str = r##"a  = 1;
+bb = 2;
+//-----
+very_long_name = 3;
+c              = 4;"##;
+
+        let result = format_text(in_str, &cfg).unwrap();
+        assert_eq(&result, out_str);
+
+        let cfg = Config::new().auto_mode(AutoMode::SimpleVarAssignment).segmentation(Segmentation::Analyzer);
+        let result = format_text("a = 1;\nbb = 2;\nint f();\nvery_long_name = 3;\nc = 4;", &cfg).unwrap();
+        assert_eq(&result, "a  = 1;\nbb = 2;\nint f();\nvery_long_name = 3;\nc              = 4;");
+
+        assert!("regex:(".parse::<Segmentation>().is_err());
+        assert!("lines".parse::<Segmentation>().is_err());
+    }
+}
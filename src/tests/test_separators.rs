@@ -15,9 +15,9 @@ in columns
 try to format it correctly
     "##;
         
-        //expected: (currently there are spaces at the end of each line except the longest one)
+        //expected:
         let out_str = r##"
-some weird   data  
+some weird   data
 in   columns
 try  to      format it correctly
     "##;
@@ -37,10 +37,10 @@ some weird, data
 in, columns
 try to, format it, correctly"##;
         
-        //expected: (currently there are spaces at the end of each line except the longest one)
+        //expected:
         let out_str = r##"
-some weird, data     
-in        , columns  
+some weird, data
+in        , columns
 try to    , format it, correctly"##;
 
         let result = run_analyzer(in_str, cfg.analyzer.as_mut(), cfg.formatter, cfg.printer);
@@ -48,6 +48,28 @@ try to    , format it, correctly"##;
         assert_eq(&result, out_str);
     }
     
+    #[test]
+    fn test_comma_rectangular() {
+        let mut cfg = do_auto_config(AutoMode::SimpleComma);
+        cfg.printer.set_rectangular(true);
+
+        //input
+        let in_str = r##"
+some weird, data
+in, columns
+try to, format it, correctly"##;
+        
+        //expected:
+        let out_str = "
+some weird, data     
+in        , columns  
+try to    , format it, correctly";
+
+        let result = run_analyzer(in_str, cfg.analyzer.as_mut(), cfg.formatter, cfg.printer);
+
+        assert_eq(&result, out_str);
+    }
+
     #[test]
     fn test_struct() {
         let mut cfg = do_auto_config(AutoMode::CLike(Some('{'), Some('}')));
@@ -60,7 +82,7 @@ try to    , format it, correctly"##;
  {"OneMore::WhoNeedsThis", &OneMore::WhoNeedsThis, "nothing", ""},
 "##;
         
-        //expected: (currently there are spaces at the end of each line except the longest one)
+        //expected:
         let out_str = r##"
     {"SomeApi::Func1"          , &SomeApi::Func1            , "{int b[, int : a]}"                 , "result: boolean"},
     {"SomeOtherApi::CoolMethod", &SomeOtherApi::CoolMyMethod, "string : nu, int : bla[, {x : all}]", "nothing"        },
@@ -84,12 +106,12 @@ try to    , format it, correctly"##;
  {"OneMore::WhoNeedsThis", &OneMore::WhoNeedsThis, "nothing", ""},
 "##;
         
-        //expected: (currently there are spaces at the end of each line except the longest one)
+        //expected:
         let out_str = r##"
-    {"SomeApi::Func1"          , &SomeApi::Func1            , "{int b[, int : a]}"                 , "result: boolean"},                     
-    {"SomeOtherApi::CoolMethod", &SomeOtherApi::CoolMyMethod, "string : nu, int : bla[, {x : all}]", "nothing"        },//comment            
+    {"SomeApi::Func1"          , &SomeApi::Func1            , "{int b[, int : a]}"                 , "result: boolean"},
+    {"SomeOtherApi::CoolMethod", &SomeOtherApi::CoolMyMethod, "string : nu, int : bla[, {x : all}]", "nothing"        },//comment
     {"JustApi::Boring"         , &JustApi::Boring           , ""                                   , "nothing"        }, //some other comment
-    {"OneMore::WhoNeedsThis"   , &OneMore::WhoNeedsThis     , "nothing"                            , ""               },                     "##;
+    {"OneMore::WhoNeedsThis"   , &OneMore::WhoNeedsThis     , "nothing"                            , ""               },"##;
 
         let result = run_analyzer(in_str, cfg.analyzer.as_mut(), cfg.formatter, cfg.printer);
 
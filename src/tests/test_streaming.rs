@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod mytests {
+    use crate::tests::mytests::assert_eq;
+    use crate::config::Config;
+    use crate::column_tools::Align;
+    use crate::segments::Segmentation;
+    use crate::streaming::format_stream;
+    use crate::streaming::format_stream_spooled;
+    use crate::format_text;
+
+    fn stream(in_str :&str, cfg :&Config) -> String {
+        let mut out : Vec<u8> = Vec::new();
+        format_stream(std::io::Cursor::new(in_str.as_bytes()), &mut out, cfg).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    //input
+    const IN_STR : &str = "a, bb, c  \r\ndddd, e, ff\n\n  x, yyy\n  zz, w\n// end\n";
+
+    #[test]
+    fn test_same_as_text() {
+        let cfgs = [
+            Config::new().separators(vec![',']).align(Align::Left),
+            Config::new().separators(vec![',']).segmentation(Segmentation::BlankLines),
+            Config::new().separators(vec![',']).segmentation(Segmentation::Indent).non_matched_as_is(true),
+            Config::new().auto(),
+        ];
+        for cfg in cfgs.iter() {
+            assert_eq(&stream(IN_STR, cfg), &format_text(IN_STR, cfg).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_spooled() {
+        let cfg = Config::new().separators(vec![',']).segmentation(Segmentation::BlankLines);
+        let mut out : Vec<u8> = Vec::new();
+        format_stream_spooled(IN_STR.as_bytes(), &mut out, &cfg).unwrap();
+        assert_eq(&String::from_utf8(out).unwrap(), &format_text(IN_STR, &cfg).unwrap());
+    }
+}
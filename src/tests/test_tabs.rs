@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod mytests {
+    use crate::tests::mytests::assert_eq;
+    use crate::config::Config;
+    use crate::auto_config::AutoMode;
+    use crate::column_tools::TabMode;
+    use crate::column_tools::expanded_width;
+    use crate::format_text;
+
+    #[test]
+    fn test_width() {
+        assert_eq!(expanded_width("\tab", 4), 6);
+        assert_eq!(expanded_width("ab\tc", 4), 5);
+        assert_eq!(expanded_width("abcd\t", 4), 8);
+        assert_eq!(expanded_width("\t", 0), 1);
+    }
+
+    #[test]
+    fn test_spaces() {
+        let cfg = Config::new().auto_mode(AutoMode::SimpleAssignment).tab_width(4);
+
+        //input
+        let in_str = "\tint x = 1;\n    long long_name = 2;\n\tint\tb = 3;";
+        
+        //expected:
+        let out_str = "\tint  x         = 1;\n    long long_name = 2;\n\tint  b         = 3;";
+
+        let result = format_text(in_str, &cfg).unwrap();
+        assert_eq(&result, out_str);
+    }
+
+    #[test]
+    fn test_smart() {
+        let cfg = Config::new().auto_mode(AutoMode::SimpleAssignment).tab_width(4).tab_mode(TabMode::Smart);
+
+        //input
+        let in_str = "\t\tint x = 1;\n        long long_name = 2;";
+        
+        //expected:
+        let out_str = "\t\tint  x         = 1;\n\t\tlong long_name = 2;";
+
+        let result = format_text(in_str, &cfg).unwrap();
+        assert_eq(&result, out_str);
+    }
+
+    #[test]
+    fn test_stops() {
+        let cfg = Config::new().auto_mode(AutoMode::SimpleAssignment).tab_width(4).tab_mode(TabMode::Stops);
+
+        //input
+        let in_str = "\tint x = 1;\n\tlong long_name = 2;";
+        
+        //expected: 'int' ends at 7 and is padded to 8 with a tab, 'x' ends at 10 and is padded to 18 with two tabs and spaces
+        let out_str = "\tint\t x\t\t   = 1;\n\tlong long_name = 2;";
+
+        let result = format_text(in_str, &cfg).unwrap();
+        assert_eq(&result, out_str);
+    }
+}
@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod mytests {
+    use crate::tests::mytests::assert_eq;
+    use crate::config::Config;
+    use crate::column_tools::Align;
+    use crate::format_text;
+
+    #[test]
+    fn test_auto() {
+        let cfg = Config::new().auto();
+        let unalign = Config::new().auto().unalign(true);
+
+        //input
+        let in_str = r##"    unsigned a   :  3; // first
+    int      bbbb : 12;
+    char     c    :  1;"##;
+
+        //expected:
+        let out_str = r##"    unsigned a : 3; // first
+    int bbbb : 12;
+    char c : 1;"##;
+
+        let unaligned = format_text(in_str, &unalign).unwrap();
+        assert_eq(&unaligned, out_str);
+        //round trip
+        assert_eq(&format_text(&unaligned, &cfg).unwrap(), &format_text(in_str, &cfg).unwrap());
+        assert_eq(&format_text(&format_text(in_str, &cfg).unwrap(), &unalign).unwrap(), out_str);
+    }
+
+    #[test]
+    fn test_table() {
+        let cfg = Config::new().auto();
+        let unalign = Config::new().auto().unalign(true);
+
+        //input
+        let in_str = r##"| Name        | Value |
+| :---------- | ----: |
+| longer name | 12345 |"##;
+
+        //expected:
+        let out_str = r##"| Name | Value |
+| :- | -: |
+| longer name | 12345 |"##;
+
+        let unaligned = format_text(in_str, &unalign).unwrap();
+        assert_eq(&unaligned, out_str);
+        assert_eq(&format_text(&unaligned, &cfg).unwrap(), in_str);
+    }
+
+    #[test]
+    fn test_csv() {
+        let cfg = Config::new().analyzer("csv").align(Align::Left);
+        let unalign = cfg.clone().unalign(true);
+
+        //input
+        let in_str = r##"a     ,"b, c",d
+eeeeee,f     ,"""""##;
+
+        //expected:
+        let out_str = r##"a,"b, c",d
+eeeeee,f,"""""##;
+
+        let unaligned = format_text(in_str, &unalign).unwrap();
+        assert_eq(&unaligned, out_str);
+        assert_eq(&format_text(&unaligned, &cfg).unwrap(), in_str);
+    }
+
+    #[test]
+    fn test_sep_config() {
+        let cfg = Config::new().separators(vec![',']).separator_config(",: :1".parse().unwrap()).align(Align::Left);
+        let unalign = cfg.clone().unalign(true);
+
+        //input
+        let in_str = r##"a   , bb, c
+dddd, e , ff"##;
+
+        //expected:
+        let out_str = r##"a, bb, c
+dddd, e, ff"##;
+
+        assert_eq(&format_text(in_str, &unalign).unwrap(), out_str);
+        //cells keep their own spacing, so only unaligning again is stable
+        assert_eq(&format_text(&format_text(out_str, &cfg).unwrap(), &unalign).unwrap(), out_str);
+    }
+
+    #[test]
+    fn test_c_like() {
+        let cfg = Config::new().auto();
+        let unalign = Config::new().auto().unalign(true);
+
+        //input
+        let in_str = r##"{"a"  , 1 , x },
+{"bbb", 22, yy},"##;
+
+        //expected:
+        let out_str = r##"{"a", 1, x},
+{"bbb", 22, yy},"##;
+
+        let unaligned = format_text(in_str, &unalign).unwrap();
+        assert_eq(&unaligned, out_str);
+        assert_eq(&format_text(&unaligned, &cfg).unwrap(), in_str);
+    }
+
+    #[test]
+    fn test_simple_comma() {
+        let cfg = Config::new().auto();
+        let unalign = Config::new().auto().unalign(true);
+
+        //input
+        let in_str = r##"in        , columns
+something , else"##;
+
+        //expected:
+        let out_str = r##"in, columns
+something, else"##;
+
+        assert_eq(&format_text(in_str, &unalign).unwrap(), out_str);
+        assert_eq(&format_text(&format_text(out_str, &cfg).unwrap(), &unalign).unwrap(), out_str);
+    }
+}
@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod mytests {
+    use crate::tests::mytests::run_analyzer;
+    use crate::tests::mytests::assert_eq;
+    use crate::auto_config::*;
+    use crate::column_tools::display_width;
+    use crate::analyzers::LineParser;
+
+    #[test]
+    fn test_width() {
+        assert_eq!(display_width("abc"), 3);
+        assert_eq!(display_width("café"), 4);
+        assert_eq!(display_width("cafe\u{301}"), 4);
+        assert_eq!(display_width("日本"), 4);
+        assert_eq!(display_width("👍"), 2);
+        assert_eq!(display_width("👨\u{200d}👩\u{200d}👧"), 2);
+    }
+
+    #[test]
+    fn test_whitespace() {
+        assert_eq!("a\u{a0}b".find_white().unwrap(), 1);
+        assert_eq!("a\u{3000}b".rfind_white().unwrap(), 3);
+        assert_eq!("é ".rfind_nwhite().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_space() {
+        let mut cfg = do_auto_config(AutoMode::SimpleSpace);
+
+        //input
+        let in_str = r##"
+名前 値 x
+café 1 y
+ab 日本語 z"##;
+        
+        //expected:
+        let out_str = r##"
+名前 値     x
+café 1      y
+ab   日本語 z"##;
+
+        let result = run_analyzer(in_str, cfg.analyzer.as_mut(), cfg.formatter, cfg.printer);
+
+        assert_eq(&result, out_str);
+    }
+
+    #[test]
+    fn test_assignment() {
+        let mut cfg = do_auto_config(AutoMode::SimpleAssignment);
+
+        //input
+        let in_str = r##"
+int café = 1;
+long naïve_value = 2;"##;
+        
+        //expected:
+        let out_str = r##"
+int  café        = 1;
+long naïve_value = 2;"##;
+
+        let result = run_analyzer(in_str, cfg.analyzer.as_mut(), cfg.formatter, cfg.printer);
+
+        assert_eq(&result, out_str);
+    }
+
+    #[test]
+    fn test_xml_non_ascii_end() {
+        let mut cfg = do_auto_config(AutoMode::Xml);
+        assert!(cfg.analyzer.can_accept("<b>日本").is_err());
+        assert!(cfg.analyzer.can_accept("<a x=\"日本\">").is_ok());
+
+        //input
+        let in_str = r##"<b x="1">日本
+<a x="日本" yy="2">"##;
+
+        //expected: the first line is kept as is
+        let out_str = r##"<b x="1">日本
+<a x="日本" yy="2" >"##;
+
+        let result = run_analyzer(in_str, cfg.analyzer.as_mut(), cfg.formatter, cfg.printer);
+
+        assert_eq(&result, out_str);
+        assert!(crate::format_text("<b>日本", &crate::Config::new().auto()).is_ok());
+    }
+}
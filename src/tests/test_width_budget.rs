@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod mytests {
+    use crate::tests::mytests::assert_eq;
+    use crate::config::Config;
+    use crate::column_tools::Overflow;
+    use crate::column_tools::truncate_to_width;
+    use crate::format_text;
+
+    //input
+    const IN_STR : &str = r##"    {"a", 1, x},
+    {"this is a very long string literal", 22, y},
+    {"ccc", 3, z},"##;
+
+    #[test]
+    fn test_max_column() {
+        let cfg = Config::new().auto().max_column_width(10);
+
+        //expected:
+        let out_str = r##"    {"a"  , 1 , x},
+    {"this is a very long string literal", 22, y},
+    {"ccc", 3 , z},"##;
+
+        assert_eq(&format_text(IN_STR, &cfg).unwrap(), out_str);
+
+        //expected:
+        let out_str = r##"    {"a"      , 1 , x},
+    {"this is…, 22, y},
+    {"ccc"    , 3 , z},"##;
+
+        assert_eq(&format_text(IN_STR, &cfg.overflow(Overflow::Truncate)).unwrap(), out_str);
+    }
+
+    #[test]
+    fn test_max_line() {
+        let cfg = Config::new().auto().max_line_width(25);
+
+        //expected:
+        let out_str = r##"    {"a"        , 1 , x},
+    {"this is a very long string literal", 22, y},
+    {"ccc"      , 3 , z},"##;
+
+        let res = format_text(IN_STR, &cfg).unwrap();
+        assert_eq(&res, out_str);
+        assert!(res.lines().filter(|l|!l.contains("long")).all(|l|l.len() <= 25));
+    }
+
+    #[test]
+    fn test_outlier_percent() {
+        let cfg = Config::new().separators(vec![',']).outlier_percent(25);
+
+        //input
+        let in_str = "a,1\nbb,2\nc,3\nlong one,4";
+        
+        //expected:
+        let out_str = "a ,1\nbb,2\nc ,3\nlong one,4";
+
+        assert_eq(&format_text(in_str, &cfg).unwrap(), out_str);
+    }
+
+    #[test]
+    fn test_outlier_percent_repeated() {
+        let cfg = Config::new().separators(vec![',']).outlier_percent(20);
+
+        //input: equal widths count once per cell
+        let in_str = "a,1\nbbb,2\nbbb,3\nc,4\nlong one,5";
+
+        //expected:
+        let out_str = " a ,1\nbbb,2\nbbb,3\n c ,4\nlong one,5";
+
+        assert_eq(&format_text(in_str, &cfg).unwrap(), out_str);
+        let mut out : Vec<u8> = Vec::new();
+        crate::streaming::format_stream(std::io::Cursor::new(in_str.as_bytes()), &mut out, &cfg).unwrap();
+        assert_eq(&String::from_utf8(out).unwrap(), out_str);
+    }
+
+    #[test]
+    fn test_zero_max_column() {
+        let args : Vec<String> = ["--max_column_width", "0"].iter().map(|s|s.to_string()).collect();
+        assert!(Config::new().parse_args(args.iter()).is_err());
+
+        //no limit through the API
+        let cfg = Config::new().separators(vec![',']).max_column_width(0).overflow(Overflow::Truncate);
+        assert_eq(&format_text("a,1\nbb,2", &cfg).unwrap(), "a ,1\nbb,2");
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate_to_width("abcdef", 4), "abc…");
+        assert_eq!(truncate_to_width("日本語", 4), "日…");
+    }
+}
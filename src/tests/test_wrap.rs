@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod mytests {
+    use crate::tests::mytests::assert_eq;
+    use crate::config::Config;
+    use crate::column_tools::Align;
+    use crate::lines::LineRange;
+    use crate::in_place::format_line_ranges;
+    use crate::format_text;
+
+    #[test]
+    fn test_comment() {
+        let cfg = Config::new().analyzer("bit_field").align(Align::Left).wrap(50);
+
+        //input
+        let in_str = r##"unsigned a : 3; // first field with a rather long description of what it does in detail
+int bbbb : 12; # second field, also described in a few more words than fit
+char c : 1;"##;
+        
+        //expected:
+        let out_str = r##"unsigned a   :3;  // first field with a rather
+                  // long description of what it
+                  // does in detail
+int      bbbb:12; # second field, also described
+                  # in a few more words than fit
+char     c   :1;"##;
+
+        assert_eq(&format_text(in_str, &cfg).unwrap(), out_str);
+    }
+
+    #[test]
+    fn test_prose() {
+        let cfg = Config::new().separators(vec![',']).align(Align::Left).prestart(true).wrap(30);
+
+        //input
+        let in_str = "    x, y, some prose that goes on and on and on and on\r\n    zz, w, short\r\n";
+        
+        //expected:
+        let out_str = "    x , y, some prose that\r\n           goes on and on and\r\n           on and on\r\n    zz, w, short\r\n";
+
+        let (res, _) = format_line_ranges(in_str, &[LineRange{begin : 1, end : 2}], &cfg).unwrap();
+        assert_eq(&res, out_str);
+    }
+}
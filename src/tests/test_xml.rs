@@ -16,9 +16,9 @@ mod mytests {
 <sosdmeothertag gggprop1="ddvaeekdljkwl1" some= "skjldksome name" andeee=" different"/>
     "##;
         
-        //expected: (currently there are spaces at the end of each line except the longest one)
+        //expected:
         let out_str = r##"
-<sometag        prop1       ="val1"           someotherprop="some other name" and_some="thing completely different" > 
+<sometag        prop1       ="val1"           someotherprop="some other name" and_some="thing completely different" >
 </sometag>
 <someothertag   gggprop1    ="ddval1"         some         ="some name"       and     =" different"                 />
 <someortag      gddddggprop1="ddval1"         some         ="dd some name"    and     =" different"                 />